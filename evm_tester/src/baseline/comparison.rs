@@ -0,0 +1,113 @@
+//!
+//! The baseline comparison.
+//!
+
+use colored::Colorize;
+
+///
+/// The comparison of the current run against the baseline.
+///
+#[derive(Debug, Default)]
+pub struct Comparison {
+    /// The cases which passed in the baseline and fail now.
+    pub regressions: Vec<String>,
+    /// The cases which failed in the baseline and pass now.
+    pub fixes: Vec<String>,
+    /// The cases which pass in both runs with different ergs or gas.
+    pub cost_changes: Vec<CostChange>,
+    /// The cases which are missing in the baseline.
+    pub appeared: Vec<String>,
+    /// The cases which are missing in the current run.
+    pub disappeared: Vec<String>,
+}
+
+///
+/// The ergs and gas change of a case passing in both runs.
+///
+#[derive(Debug)]
+pub struct CostChange {
+    /// The case name.
+    pub name: String,
+    /// The baseline ergs and gas.
+    pub baseline: (u64, u64),
+    /// The current ergs and gas.
+    pub current: (u64, u64),
+}
+
+impl CostChange {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, baseline: (u64, u64), current: (u64, u64)) -> Self {
+        Self {
+            name,
+            baseline,
+            current,
+        }
+    }
+}
+
+impl Comparison {
+    ///
+    /// Whether any case has regressed.
+    ///
+    pub fn is_regressed(&self) -> bool {
+        !self.regressions.is_empty()
+    }
+
+    ///
+    /// Sorts every list by the case name.
+    ///
+    pub fn sort(&mut self) {
+        self.regressions.sort();
+        self.fixes.sort();
+        self.cost_changes.sort_by(|a, b| a.name.cmp(&b.name));
+        self.appeared.sort();
+        self.disappeared.sort();
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} {} regressions, {} fixes, {} cost changes, {} appeared, {} disappeared",
+            "Baseline comparison:".bright_white().bold(),
+            self.regressions.len(),
+            self.fixes.len(),
+            self.cost_changes.len(),
+            self.appeared.len(),
+            self.disappeared.len(),
+        )?;
+        for name in self.regressions.iter() {
+            writeln!(f, "{:>11} {name}", "REGRESSED".bright_red())?;
+        }
+        for name in self.fixes.iter() {
+            writeln!(f, "{:>11} {name}", "FIXED".green())?;
+        }
+        for change in self.cost_changes.iter() {
+            let ergs_delta = (change.current.0 as i128) - (change.baseline.0 as i128);
+            let gas_delta = (change.current.1 as i128) - (change.baseline.1 as i128);
+            writeln!(
+                f,
+                "{:>11} {} (ergs {} -> {} {:+}, gas {} -> {} {:+})",
+                "COST".bright_yellow(),
+                change.name,
+                change.baseline.0,
+                change.current.0,
+                ergs_delta,
+                change.baseline.1,
+                change.current.1,
+                gas_delta,
+            )?;
+        }
+        for name in self.appeared.iter() {
+            writeln!(f, "{:>11} {name}", "APPEARED".bright_white())?;
+        }
+        for name in self.disappeared.iter() {
+            writeln!(f, "{:>11} {name}", "DISAPPEARED".bright_black())?;
+        }
+
+        Ok(())
+    }
+}
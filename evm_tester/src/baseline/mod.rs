@@ -0,0 +1,253 @@
+//!
+//! The baseline run.
+//!
+
+pub mod comparison;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::summary::breakdown::axis::Axis as BreakdownAxis;
+use crate::summary::breakdown::Breakdown;
+use crate::summary::element::outcome::Outcome;
+use crate::summary::element::Element;
+
+use self::comparison::Comparison;
+use self::comparison::CostChange;
+
+///
+/// The baseline run.
+///
+/// Is read from the JSON results of an earlier run, written with `--output-json`.
+///
+#[derive(Debug)]
+pub struct Baseline {
+    /// The earlier run elements by name.
+    elements: HashMap<String, Element>,
+}
+
+impl Baseline {
+    ///
+    /// Reads the baseline from the JSON results file.
+    ///
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let results = std::fs::read_to_string(path).map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to read the baseline `{}`: {error}",
+                path.to_string_lossy()
+            )
+        })?;
+        let mut results: serde_json::Value = serde_json::from_str(results.as_str())?;
+        let elements: Vec<Element> = match results.get_mut("elements") {
+            Some(elements) => serde_json::from_value(elements.take())?,
+            None => anyhow::bail!("The baseline `{}` has no elements", path.to_string_lossy()),
+        };
+
+        let elements = elements
+            .into_iter()
+            .map(|element| (element.name.clone(), element))
+            .collect();
+        Ok(Self { elements })
+    }
+
+    ///
+    /// Returns the pass-rate breakdown of the baseline by the specified axis.
+    ///
+    pub fn breakdown(&self, axis: BreakdownAxis) -> Breakdown {
+        Breakdown::new(axis, self.elements.values())
+    }
+
+    ///
+    /// Compares the current run elements against the baseline.
+    ///
+    pub fn compare(&self, elements: &[Element]) -> Comparison {
+        let mut comparison = Comparison::default();
+
+        for element in elements.iter() {
+            let baseline = match self.elements.get(element.name.as_str()) {
+                Some(baseline) => baseline,
+                None => {
+                    comparison.appeared.push(element.name.clone());
+                    continue;
+                }
+            };
+
+            match (&baseline.outcome, &element.outcome) {
+                (Outcome::Passed { .. }, Outcome::Failed { .. } | Outcome::Invalid { .. })
+                    if element.expected_failure.is_none() =>
+                {
+                    comparison.regressions.push(element.name.clone())
+                }
+                (Outcome::Failed { .. } | Outcome::Invalid { .. }, Outcome::Passed { .. }) => {
+                    comparison.fixes.push(element.name.clone())
+                }
+                (
+                    Outcome::Passed {
+                        variant: baseline_variant,
+                        ..
+                    },
+                    Outcome::Passed { variant, .. },
+                ) => {
+                    if let (Some((_, baseline_ergs, baseline_gas)), Some((_, ergs, gas))) =
+                        (baseline_variant.costs(), variant.costs())
+                    {
+                        if baseline_ergs != ergs || baseline_gas != gas {
+                            comparison.cost_changes.push(CostChange::new(
+                                element.name.clone(),
+                                (baseline_ergs, baseline_gas),
+                                (ergs, gas),
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let names: HashSet<&str> = elements
+            .iter()
+            .map(|element| element.name.as_str())
+            .collect();
+        comparison.disappeared = self
+            .elements
+            .keys()
+            .filter(|name| !names.contains(name.as_str()))
+            .cloned()
+            .collect();
+
+        comparison.sort();
+        comparison
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::summary::element::fixtures;
+    use crate::summary::element::Element;
+
+    use super::Baseline;
+
+    ///
+    /// Creates the baseline from the elements.
+    ///
+    fn baseline(elements: Vec<Element>) -> Baseline {
+        Baseline {
+            elements: elements
+                .into_iter()
+                .map(|element| (element.name.clone(), element))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn regressions() {
+        let baseline = baseline(vec![
+            fixtures::passed("failed"),
+            fixtures::passed("invalid"),
+        ]);
+        let comparison =
+            baseline.compare(&[fixtures::failed("failed"), fixtures::invalid("invalid")]);
+
+        assert_eq!(comparison.regressions, vec!["failed", "invalid"]);
+        assert!(comparison.is_regressed());
+        assert!(comparison.fixes.is_empty());
+        assert!(comparison.cost_changes.is_empty());
+    }
+
+    #[test]
+    fn expected_failures_do_not_regress() {
+        let baseline = baseline(vec![
+            fixtures::passed("failed"),
+            fixtures::passed("invalid"),
+        ]);
+        let comparison = baseline.compare(&[
+            fixtures::expected_failure(fixtures::failed("failed")),
+            fixtures::expected_failure(fixtures::invalid("invalid")),
+        ]);
+
+        assert!(comparison.regressions.is_empty());
+        assert!(!comparison.is_regressed());
+    }
+
+    #[test]
+    fn fixes() {
+        let baseline = baseline(vec![
+            fixtures::failed("failed"),
+            fixtures::invalid("invalid"),
+        ]);
+        let comparison =
+            baseline.compare(&[fixtures::passed("failed"), fixtures::passed("invalid")]);
+
+        assert_eq!(comparison.fixes, vec!["failed", "invalid"]);
+        assert!(comparison.regressions.is_empty());
+        assert!(!comparison.is_regressed());
+    }
+
+    #[test]
+    fn cost_changes() {
+        let baseline = baseline(vec![
+            fixtures::passed_with_cost("ergs", 10, 5),
+            fixtures::passed_with_cost("gas", 10, 5),
+            fixtures::passed_with_cost("same", 10, 5),
+        ]);
+        let comparison = baseline.compare(&[
+            fixtures::passed_with_cost("ergs", 12, 5),
+            fixtures::passed_with_cost("gas", 10, 3),
+            fixtures::passed_with_cost("same", 10, 5),
+        ]);
+
+        assert_eq!(comparison.cost_changes.len(), 2);
+        assert_eq!(comparison.cost_changes[0].name, "ergs");
+        assert_eq!(comparison.cost_changes[0].baseline, (10, 5));
+        assert_eq!(comparison.cost_changes[0].current, (12, 5));
+        assert_eq!(comparison.cost_changes[1].name, "gas");
+        assert_eq!(comparison.cost_changes[1].baseline, (10, 5));
+        assert_eq!(comparison.cost_changes[1].current, (10, 3));
+        assert!(!comparison.is_regressed());
+    }
+
+    #[test]
+    fn unchanged_failures() {
+        let baseline = baseline(vec![
+            fixtures::failed("failed"),
+            fixtures::invalid("invalid"),
+        ]);
+        let comparison =
+            baseline.compare(&[fixtures::invalid("failed"), fixtures::failed("invalid")]);
+
+        assert!(comparison.regressions.is_empty());
+        assert!(comparison.fixes.is_empty());
+        assert!(comparison.cost_changes.is_empty());
+    }
+
+    #[test]
+    fn appeared_and_disappeared() {
+        let baseline = baseline(vec![fixtures::passed("kept"), fixtures::passed("removed")]);
+        let comparison = baseline.compare(&[fixtures::passed("kept"), fixtures::failed("added")]);
+
+        assert_eq!(comparison.appeared, vec!["added"]);
+        assert_eq!(comparison.disappeared, vec!["removed"]);
+        assert!(!comparison.is_regressed());
+    }
+
+    #[test]
+    fn read_results() {
+        let path = std::env::temp_dir().join(format!(
+            "evm-tester-baseline-results-{}.json",
+            std::process::id()
+        ));
+        let results = serde_json::json!({
+            "elements": [fixtures::passed_with_cost("passed", 10, 5), fixtures::failed("failed")],
+        });
+        std::fs::write(path.as_path(), results.to_string()).expect("Failed to write the results");
+
+        let baseline = Baseline::read(path.as_path()).expect("Failed to read the baseline");
+        let _ = std::fs::remove_file(path);
+
+        let comparison =
+            baseline.compare(&[fixtures::failed("passed"), fixtures::passed("failed")]);
+        assert_eq!(comparison.regressions, vec!["passed"]);
+        assert_eq!(comparison.fixes, vec!["failed"]);
+    }
+}
@@ -0,0 +1,148 @@
+//!
+//! The emulator cost benchmark comparison.
+//!
+
+use colored::Colorize;
+
+///
+/// The comparison of the current benchmark against the stored one.
+///
+#[derive(Debug, Default)]
+pub struct Comparison {
+    /// The threshold of the relative change in percent.
+    pub threshold: f64,
+    /// The cases whose ergs or cycles have grown by more than the threshold.
+    pub regressions: Vec<CostDelta>,
+    /// The cases whose ergs or cycles have shrunk by more than the threshold, without regressing.
+    pub improvements: Vec<CostDelta>,
+}
+
+///
+/// The ergs and cycles change of a case present in both benchmarks.
+///
+#[derive(Debug)]
+pub struct CostDelta {
+    /// The case name.
+    pub name: String,
+    /// The stored ergs and cycles.
+    pub baseline: (u64, usize),
+    /// The current ergs and cycles.
+    pub current: (u64, usize),
+}
+
+impl CostDelta {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, baseline: (u64, usize), current: (u64, usize)) -> Self {
+        Self {
+            name,
+            baseline,
+            current,
+        }
+    }
+
+    ///
+    /// The relative change of the ergs in percent.
+    ///
+    pub fn ergs_change(&self) -> f64 {
+        Self::change(self.baseline.0 as f64, self.current.0 as f64)
+    }
+
+    ///
+    /// The relative change of the cycles in percent.
+    ///
+    pub fn cycles_change(&self) -> f64 {
+        Self::change(self.baseline.1 as f64, self.current.1 as f64)
+    }
+
+    ///
+    /// Returns the relative change in percent, infinite if the value has grown from zero.
+    ///
+    fn change(baseline: f64, current: f64) -> f64 {
+        if baseline == 0.0 {
+            if current == 0.0 {
+                0.0
+            } else {
+                f64::INFINITY
+            }
+        } else {
+            (current - baseline) * 100.0 / baseline
+        }
+    }
+}
+
+impl Comparison {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Sorts the changes into regressions and improvements, dropping the ones within the threshold.
+    ///
+    pub fn push(&mut self, delta: CostDelta) {
+        let (ergs_change, cycles_change) = (delta.ergs_change(), delta.cycles_change());
+        if ergs_change > self.threshold || cycles_change > self.threshold {
+            self.regressions.push(delta);
+        } else if ergs_change < -self.threshold || cycles_change < -self.threshold {
+            self.improvements.push(delta);
+        }
+    }
+
+    ///
+    /// Whether any case has regressed.
+    ///
+    pub fn is_regressed(&self) -> bool {
+        !self.regressions.is_empty()
+    }
+
+    ///
+    /// Sorts the regressions and improvements by the ergs change, the largest first.
+    ///
+    pub fn sort(&mut self) {
+        self.regressions
+            .sort_by(|a, b| b.ergs_change().total_cmp(&a.ergs_change()));
+        self.improvements
+            .sort_by(|a, b| a.ergs_change().total_cmp(&b.ergs_change()));
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} {} regressions, {} improvements beyond {}%",
+            "Benchmark comparison:".bright_white().bold(),
+            self.regressions.len(),
+            self.improvements.len(),
+            self.threshold,
+        )?;
+        for (outcome, deltas) in [
+            ("REGRESSED".bright_red(), &self.regressions),
+            ("IMPROVED".green(), &self.improvements),
+        ] {
+            for delta in deltas.iter() {
+                writeln!(
+                    f,
+                    "{:>9} {} (ergs {} -> {} {:+.2}%, cycles {} -> {} {:+.2}%)",
+                    outcome,
+                    delta.name,
+                    delta.baseline.0,
+                    delta.current.0,
+                    delta.ergs_change(),
+                    delta.baseline.1,
+                    delta.current.1,
+                    delta.cycles_change(),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
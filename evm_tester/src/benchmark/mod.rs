@@ -0,0 +1,269 @@
+//!
+//! The emulator cost benchmark.
+//!
+
+pub mod comparison;
+pub mod record;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::summary::element::outcome::passed_variant::PassedVariant;
+use crate::summary::element::outcome::Outcome;
+use crate::summary::element::Element;
+
+use self::comparison::Comparison;
+use self::comparison::CostDelta;
+use self::record::Record;
+
+///
+/// The emulator cost benchmark.
+///
+/// Relates the EraVM ergs and cycles of every passed call to the EVM gas it uses.
+///
+#[derive(Debug)]
+pub struct Benchmark {
+    /// The records of the passed calls which have used any gas.
+    records: Vec<Record>,
+}
+
+///
+/// The per-directory cost aggregate.
+///
+#[derive(Debug, Default)]
+struct DirectoryAggregate {
+    /// The number of cases.
+    cases: usize,
+    /// The total gas used.
+    gas: u128,
+    /// The total ergs used.
+    ergs: u128,
+    /// The total cycles.
+    cycles: u128,
+}
+
+impl Benchmark {
+    /// The percentiles shown in the report.
+    pub const PERCENTILES: [usize; 4] = [50, 90, 99, 100];
+
+    /// The number of the worst outliers shown in the report.
+    pub const OUTLIERS: usize = 10;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(elements: &[Element]) -> Self {
+        let mut records: Vec<Record> = elements
+            .iter()
+            .filter_map(|element| match element.outcome {
+                Outcome::Passed {
+                    variant: PassedVariant::Runtime { cycles, ergs, gas },
+                    ..
+                } if !gas.is_zero() => Some(Record::new(
+                    element.name.clone(),
+                    element.directory.clone(),
+                    gas.low_u64(),
+                    ergs,
+                    cycles,
+                )),
+                _ => None,
+            })
+            .collect();
+        records.sort_by(|a, b| {
+            a.directory
+                .cmp(&b.directory)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Self { records }
+    }
+
+    ///
+    /// Reads the benchmark stored with `write_json`.
+    ///
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let benchmark = std::fs::read_to_string(path).map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to read the benchmark `{}`: {error}",
+                path.to_string_lossy()
+            )
+        })?;
+        let records: Vec<Record> = serde_json::from_str(benchmark.as_str())?;
+        Ok(Self { records })
+    }
+
+    ///
+    /// Writes the records to the specified file as JSON, to be compared against later.
+    ///
+    pub fn write_json(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(&self.records)?).map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to write the benchmark to `{}`: {error}",
+                path.to_string_lossy()
+            )
+        })
+    }
+
+    ///
+    /// Compares the ergs and cycles of the cases present in both benchmarks.
+    ///
+    /// The threshold is the relative change in percent which is tolerated in either direction.
+    ///
+    pub fn compare(&self, baseline: &Self, threshold: f64) -> Comparison {
+        let baseline: HashMap<&str, &Record> = baseline
+            .records
+            .iter()
+            .map(|record| (record.name.as_str(), record))
+            .collect();
+
+        let mut comparison = Comparison::new(threshold);
+        for record in self.records.iter() {
+            if let Some(baseline) = baseline.get(record.name.as_str()) {
+                comparison.push(CostDelta::new(
+                    record.name.clone(),
+                    (baseline.ergs, baseline.cycles),
+                    (record.ergs, record.cycles),
+                ));
+            }
+        }
+        comparison.sort();
+        comparison
+    }
+
+    ///
+    /// Writes the records with their ratios to the specified file as CSV.
+    ///
+    pub fn write_csv(&self, path: &Path) -> anyhow::Result<()> {
+        let mut csv = String::from("directory,name,gas,ergs,cycles,ergs_per_gas,cycles_per_gas\n");
+        for record in self.records.iter() {
+            csv.push_str(
+                format!(
+                    "{},{},{},{},{},{:.4},{:.4}\n",
+                    Self::escape(record.directory.as_str()),
+                    Self::escape(record.name.as_str()),
+                    record.gas,
+                    record.ergs,
+                    record.cycles,
+                    record.ergs_per_gas(),
+                    record.cycles_per_gas(),
+                )
+                .as_str(),
+            );
+        }
+        std::fs::write(path, csv).map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to write the benchmark to `{}`: {error}",
+                path.to_string_lossy()
+            )
+        })
+    }
+
+    ///
+    /// Returns the per-directory aggregates.
+    ///
+    fn aggregates(&self) -> BTreeMap<&str, DirectoryAggregate> {
+        let mut aggregates: BTreeMap<&str, DirectoryAggregate> = BTreeMap::new();
+        for record in self.records.iter() {
+            let aggregate = aggregates.entry(record.directory.as_str()).or_default();
+            aggregate.cases += 1;
+            aggregate.gas += record.gas as u128;
+            aggregate.ergs += record.ergs as u128;
+            aggregate.cycles += record.cycles as u128;
+        }
+        aggregates
+    }
+
+    ///
+    /// Returns the nearest-rank percentile of the sorted values.
+    ///
+    fn percentile(sorted: &[f64], percentile: usize) -> f64 {
+        let rank = (percentile * sorted.len()).div_ceil(100).max(1);
+        sorted[rank - 1]
+    }
+
+    ///
+    /// Quotes the CSV field if it contains the special characters.
+    ///
+    fn escape(value: &str) -> String {
+        if value.contains([',', '"', '\n']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_owned()
+        }
+    }
+}
+
+impl std::fmt::Display for Benchmark {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} {} passed calls",
+            "Emulator costs:".bright_white().bold(),
+            self.records.len(),
+        )?;
+        if self.records.is_empty() {
+            return Ok(());
+        }
+
+        let mut ergs_per_gas: Vec<f64> = self.records.iter().map(Record::ergs_per_gas).collect();
+        ergs_per_gas.sort_by(f64::total_cmp);
+        let mut cycles_per_gas: Vec<f64> =
+            self.records.iter().map(Record::cycles_per_gas).collect();
+        cycles_per_gas.sort_by(f64::total_cmp);
+
+        write!(f, "{:<12}", "RATIO")?;
+        for percentile in Self::PERCENTILES.iter() {
+            write!(f, " {:>10}", format!("P{percentile}"))?;
+        }
+        writeln!(f)?;
+        for (name, values) in [("ERGS/GAS", &ergs_per_gas), ("CYCLES/GAS", &cycles_per_gas)] {
+            write!(f, "{name:<12}")?;
+            for percentile in Self::PERCENTILES.into_iter() {
+                write!(f, " {:>10.2}", Self::percentile(values, percentile))?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<48} {:>8} {:>16} {:>16} {:>16} {:>10} {:>10}",
+            "DIRECTORY", "CASES", "GAS", "ERGS", "CYCLES", "ERGS/GAS", "CYCLES/GAS",
+        )?;
+        for (directory, aggregate) in self.aggregates().into_iter() {
+            writeln!(
+                f,
+                "{:<48} {:>8} {:>16} {:>16} {:>16} {:>10.2} {:>10.2}",
+                directory,
+                aggregate.cases,
+                aggregate.gas,
+                aggregate.ergs,
+                aggregate.cycles,
+                (aggregate.ergs as f64) / (aggregate.gas as f64),
+                (aggregate.cycles as f64) / (aggregate.gas as f64),
+            )?;
+        }
+
+        let mut outliers: Vec<&Record> = self.records.iter().collect();
+        outliers.sort_by(|a, b| b.ergs_per_gas().total_cmp(&a.ergs_per_gas()));
+        writeln!(f)?;
+        writeln!(f, "{}", "The highest ergs per gas:".bright_white().bold())?;
+        for record in outliers.into_iter().take(Self::OUTLIERS) {
+            writeln!(
+                f,
+                "{:>10} {}/{} (gas {}, ergs {}, cycles {}, cycles/gas {:.2})",
+                format!("{:.2}", record.ergs_per_gas()).bright_red(),
+                record.directory,
+                record.name,
+                record.gas,
+                record.ergs,
+                record.cycles,
+                record.cycles_per_gas(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
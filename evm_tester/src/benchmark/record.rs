@@ -0,0 +1,52 @@
+//!
+//! The emulator cost benchmark record.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The costs of a single passed case.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    /// The case name.
+    pub name: String,
+    /// The test directory.
+    pub directory: String,
+    /// The EVM gas used.
+    pub gas: u64,
+    /// The EraVM ergs used.
+    pub ergs: u64,
+    /// The EraVM execution cycles.
+    pub cycles: usize,
+}
+
+impl Record {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, directory: String, gas: u64, ergs: u64, cycles: usize) -> Self {
+        Self {
+            name,
+            directory,
+            gas,
+            ergs,
+            cycles,
+        }
+    }
+
+    ///
+    /// The number of ergs spent per unit of gas.
+    ///
+    pub fn ergs_per_gas(&self) -> f64 {
+        (self.ergs as f64) / (self.gas as f64)
+    }
+
+    ///
+    /// The number of cycles spent per unit of gas.
+    ///
+    pub fn cycles_per_gas(&self) -> f64 {
+        (self.cycles as f64) / (self.gas as f64)
+    }
+}
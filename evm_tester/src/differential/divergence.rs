@@ -0,0 +1,50 @@
+//!
+//! The differential testing divergence.
+//!
+
+use crate::summary::element::outcome::category::Category;
+
+///
+/// The differential testing divergence.
+///
+/// Describes a single value which differs between the emulator and the reference EVM.
+///
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    /// The failure category of the diverging value.
+    pub category: Category,
+    /// The diverging value description, e.g. `balance of 0x...`.
+    pub subject: String,
+    /// The value produced by the emulator.
+    pub emulator: String,
+    /// The value produced by the reference EVM.
+    pub reference: String,
+}
+
+impl Divergence {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new<E, R>(category: Category, subject: String, emulator: E, reference: R) -> Self
+    where
+        E: std::fmt::Debug,
+        R: std::fmt::Debug,
+    {
+        Self {
+            category,
+            subject,
+            emulator: format!("{emulator:?}"),
+            reference: format!("{reference:?}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: emulator {}, reference {}",
+            self.subject, self.emulator, self.reference
+        )
+    }
+}
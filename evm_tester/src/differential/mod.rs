@@ -0,0 +1,258 @@
+//!
+//! The differential comparison of the emulator against the reference EVM.
+//!
+
+pub mod divergence;
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use crate::summary::element::outcome::category::Category;
+use crate::vm::evm_account::EvmAccount;
+use crate::vm::execution_result::ExecutionResult;
+
+use self::divergence::Divergence;
+
+///
+/// The differential comparison of the emulator against the reference EVM.
+///
+#[derive(Debug, Default)]
+pub struct Differential {
+    /// The found divergences.
+    pub divergences: Vec<Divergence>,
+}
+
+impl Differential {
+    /// The success flag divergence subject.
+    pub const SUBJECT_SUCCESS: &'static str = "success";
+
+    /// The gas used divergence subject.
+    pub const SUBJECT_GAS_USED: &'static str = "gas used";
+
+    /// The return data divergence subject.
+    pub const SUBJECT_RETURN_DATA: &'static str = "return data";
+
+    /// The return data size divergence subject.
+    pub const SUBJECT_RETURN_DATA_SIZE: &'static str = "return data size";
+
+    /// The logs divergence subject.
+    pub const SUBJECT_LOGS: &'static str = "logs";
+
+    ///
+    /// Compares the execution results and the complete resulting account states.
+    ///
+    /// The return data is not compared for deploy transactions, as the emulator returns the
+    /// address and the reference EVM the code, so the code at the `deployed_address` is compared
+    /// instead. The `ignored_addresses` accounts are not compared at all.
+    ///
+    pub fn compare(
+        emulator: &ExecutionResult,
+        emulator_state: &HashMap<web3::types::Address, EvmAccount>,
+        reference: &ExecutionResult,
+        reference_state: &HashMap<web3::types::Address, EvmAccount>,
+        deployed_address: Option<web3::types::Address>,
+        ignored_addresses: &[web3::types::Address],
+    ) -> Self {
+        let mut differential = Self::default();
+
+        if emulator.output.exception != reference.output.exception {
+            differential.push(
+                Category::Exception,
+                Self::SUBJECT_SUCCESS.to_owned(),
+                !emulator.output.exception,
+                !reference.output.exception,
+            );
+        }
+
+        if emulator.gas != reference.gas {
+            differential.push(
+                Category::Gas,
+                Self::SUBJECT_GAS_USED.to_owned(),
+                emulator.gas,
+                reference.gas,
+            );
+        }
+
+        let empty_account = EvmAccount::default();
+        match deployed_address {
+            Some(address) => {
+                let emulator_code = &emulator_state.get(&address).unwrap_or(&empty_account).code;
+                let reference_code = &reference_state.get(&address).unwrap_or(&empty_account).code;
+                if emulator_code != reference_code {
+                    differential.push_code(
+                        format!("deployed code of {address:?}"),
+                        emulator_code.as_slice(),
+                        reference_code.as_slice(),
+                    );
+                }
+            }
+            None => {
+                if emulator.output.return_data_size != reference.output.return_data_size {
+                    differential.push(
+                        Category::ReturnData,
+                        Self::SUBJECT_RETURN_DATA_SIZE.to_owned(),
+                        emulator.output.return_data_size,
+                        reference.output.return_data_size,
+                    );
+                }
+
+                if emulator.output.return_data != reference.output.return_data {
+                    differential.push(
+                        Category::ReturnData,
+                        Self::SUBJECT_RETURN_DATA.to_owned(),
+                        &emulator.output.return_data,
+                        &reference.output.return_data,
+                    );
+                }
+            }
+        }
+
+        if emulator.output.events != reference.output.events {
+            differential.push(
+                Category::Logs,
+                Self::SUBJECT_LOGS.to_owned(),
+                &emulator.output.events,
+                &reference.output.events,
+            );
+        }
+
+        let addresses: BTreeSet<web3::types::Address> = emulator_state
+            .keys()
+            .chain(reference_state.keys())
+            .filter(|address| !ignored_addresses.contains(address))
+            .copied()
+            .collect();
+        for address in addresses.into_iter() {
+            differential.compare_accounts(
+                address,
+                emulator_state.get(&address).unwrap_or(&empty_account),
+                reference_state.get(&address).unwrap_or(&empty_account),
+                Some(address) == deployed_address,
+            );
+        }
+
+        differential
+    }
+
+    ///
+    /// Whether the emulator and the reference EVM agree.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    ///
+    /// Returns the failure category of the first divergence.
+    ///
+    pub fn category(&self) -> Category {
+        self.divergences
+            .first()
+            .map(|divergence| divergence.category)
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Returns the values produced by the emulator, one divergence per line.
+    ///
+    pub fn emulator_description(&self) -> String {
+        self.divergences
+            .iter()
+            .map(|divergence| format!("{}: {}", divergence.subject, divergence.emulator))
+            .collect::<Vec<String>>()
+            .join("\n   ")
+    }
+
+    ///
+    /// Returns the values produced by the reference EVM, one divergence per line.
+    ///
+    pub fn reference_description(&self) -> String {
+        self.divergences
+            .iter()
+            .map(|divergence| format!("{}: {}", divergence.subject, divergence.reference))
+            .collect::<Vec<String>>()
+            .join("\n   ")
+    }
+
+    ///
+    /// Compares the account states.
+    ///
+    /// Missing accounts are compared as empty ones, and missing storage slots as zeros.
+    /// The code of the deployed contract is skipped, as it has been compared already.
+    ///
+    fn compare_accounts(
+        &mut self,
+        address: web3::types::Address,
+        emulator: &EvmAccount,
+        reference: &EvmAccount,
+        is_deployed: bool,
+    ) {
+        if emulator.balance != reference.balance {
+            self.push(
+                Category::Balance,
+                format!("balance of {address:?}"),
+                emulator.balance,
+                reference.balance,
+            );
+        }
+
+        if emulator.nonce != reference.nonce {
+            self.push(
+                Category::Nonce,
+                format!("nonce of {address:?}"),
+                emulator.nonce,
+                reference.nonce,
+            );
+        }
+
+        if !is_deployed && emulator.code != reference.code {
+            self.push_code(
+                format!("code of {address:?}"),
+                emulator.code.as_slice(),
+                reference.code.as_slice(),
+            );
+        }
+
+        let keys: BTreeSet<web3::types::U256> = emulator
+            .storage
+            .keys()
+            .chain(reference.storage.keys())
+            .copied()
+            .collect();
+        for key in keys.into_iter() {
+            let emulator_value = emulator.storage.get(&key).copied().unwrap_or_default();
+            let reference_value = reference.storage.get(&key).copied().unwrap_or_default();
+            if emulator_value != reference_value {
+                self.push(
+                    Category::Storage,
+                    format!("storage of {address:?}, {key:#x}"),
+                    emulator_value,
+                    reference_value,
+                );
+            }
+        }
+    }
+
+    ///
+    /// Adds a divergence.
+    ///
+    fn push<E, R>(&mut self, category: Category, subject: String, emulator: E, reference: R)
+    where
+        E: std::fmt::Debug,
+        R: std::fmt::Debug,
+    {
+        self.divergences
+            .push(Divergence::new(category, subject, emulator, reference));
+    }
+
+    ///
+    /// Adds a code divergence, with the code in hexadecimal.
+    ///
+    fn push_code(&mut self, subject: String, emulator: &[u8], reference: &[u8]) {
+        self.divergences.push(Divergence {
+            category: Category::Code,
+            subject,
+            emulator: format!("0x{}", hex::encode(emulator)),
+            reference: format!("0x{}", hex::encode(reference)),
+        });
+    }
+}
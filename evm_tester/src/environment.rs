@@ -9,6 +9,10 @@
 pub enum Environment {
     /// The EraVM-based EVM emulator.
     EVMEmulator,
+    /// The revm reference EVM implementation.
+    Revm,
+    /// The SputnikVM reference EVM implementation.
+    SputnikVM,
 }
 
 impl std::str::FromStr for Environment {
@@ -17,10 +21,12 @@ impl std::str::FromStr for Environment {
     fn from_str(string: &str) -> Result<Self, Self::Err> {
         match string {
             "EVMEmulator" => Ok(Self::EVMEmulator),
+            "Revm" => Ok(Self::Revm),
+            "SputnikVM" => Ok(Self::SputnikVM),
             string => anyhow::bail!(
                 "Unknown environment `{}`. Supported environments: {:?}",
                 string,
-                vec![Self::EVMEmulator]
+                vec![Self::EVMEmulator, Self::Revm, Self::SputnikVM]
                     .into_iter()
                     .map(|element| element.to_string())
                     .collect::<Vec<String>>()
@@ -34,6 +40,8 @@ impl std::fmt::Display for Environment {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::EVMEmulator => write!(f, "EVMEmulator"),
+            Self::Revm => write!(f, "Revm"),
+            Self::SputnikVM => write!(f, "SputnikVM"),
         }
     }
 }
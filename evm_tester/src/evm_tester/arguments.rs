@@ -2,6 +2,8 @@
 //! The evm tester arguments.
 //!
 
+use std::path::PathBuf;
+
 use structopt::StructOpt;
 
 ///
@@ -26,19 +28,222 @@ pub struct Arguments {
     #[structopt(short = "g", long = "group")]
     pub groups: Vec<String>,
 
+    /// Prints only the failures of the specified categories: `balance`, `nonce`, `code`,
+    /// `storage`, `exception`, `return-data`, `gas`, `logs`, `state-root`, `invalid`, `panic`
+    /// or `other`.
+    #[structopt(long = "failure-category")]
+    pub failure_categories: Vec<evm_tester::FailureCategory>,
+
     /// Sets the number of threads, which execute the tests concurrently.
     #[structopt(short = "t", long = "threads")]
     pub threads: Option<usize>,
 
     /// Specify the environment to run tests on.
-    /// Available arguments: `EVMEmulator`.
+    /// Available arguments: `EVMEmulator`, `Revm`, `SputnikVM`.
     /// The default value is EVMEmulator
     #[structopt(long = "environment")]
     pub environment: Option<evm_tester::Environment>,
 
+    /// Runs every test on both the EVM emulator and revm, comparing the complete resulting states
+    /// instead of the test expectations.
+    /// Cannot be combined with `--environment`, `--trace`, `--export-failures` or the other
+    /// comparison modes.
+    #[structopt(
+        long = "differential",
+        conflicts_with_all = &["environment", "gas-report", "gas-sweep", "trace", "export-failures"]
+    )]
+    pub differential: bool,
+
+    /// Runs every call test on both the EVM emulator and the reference EVM, reporting the cases
+    /// where the emulator charges a different amount of gas, with per-directory aggregates.
+    /// The reference EVM is selected by `--environment`, either `Revm` (the default) or
+    /// `SputnikVM`. The deploy tests are excluded, as the emulator does not report their gas.
+    /// Cannot be combined with `--trace`, `--export-failures` or the other comparison modes.
+    #[structopt(
+        long = "gas-report",
+        conflicts_with_all = &["gas-sweep", "trace", "export-failures"]
+    )]
+    pub gas_report: bool,
+
+    /// Reruns every test on both the EVM emulator and revm at the gas limits around the minimum
+    /// passing gas, reporting the first limit where their success or state differs.
+    /// Cannot be combined with `--environment`, `--trace`, `--export-failures` or the other
+    /// comparison modes.
+    #[structopt(
+        long = "gas-sweep",
+        conflicts_with_all = &["environment", "trace", "export-failures"]
+    )]
+    pub gas_sweep: bool,
+
+    /// Writes the EIP-3155 trace of every executed case to `./trace/eip3155/`.
+    /// The EVM emulator steps are only traced with the emulator built with step logging,
+    /// see the README. With the stock emulator, only the summary line of every case is written.
+    #[structopt(long = "trace")]
+    pub trace: bool,
+
+    /// Checks the post-state root of every case whose filler expectations match against the test.
+    /// Is off by default, as the emulator state only covers the accounts known to the tester, so
+    /// the accounts only touched during the execution may be missing from its roots.
+    #[structopt(long = "state-root")]
+    pub state_root: bool,
+
+    /// Exports every failing case to the specified directory as a reproducer: a single-case state
+    /// test with its filler and a manifest with the system contract hashes and tester version.
+    #[structopt(long = "export-failures")]
+    pub export_failures: Option<PathBuf>,
+
+    /// Groups the failures by their category, last opcode, revert reason and error, printing every
+    /// group with a representative case.
+    #[structopt(long = "fingerprints")]
+    pub fingerprints: bool,
+
+    /// Prints the specified number of the slowest test files and cases, and limits the test files
+    /// in the `--output-json` results to as many, 20 by default.
+    #[structopt(long = "slowest")]
+    pub slowest: Option<usize>,
+
+    /// Writes every test outcome with its details to the specified file as JSON.
+    #[structopt(long = "output-json")]
+    pub output_json: Option<PathBuf>,
+
+    /// Writes the test outcomes to the specified file as a JUnit XML report.
+    #[structopt(long = "junit")]
+    pub junit: Option<PathBuf>,
+
+    /// Compares the run against the JSON results of an earlier run, failing only on regressions.
+    #[structopt(long = "baseline")]
+    pub baseline: Option<PathBuf>,
+
+    /// Prints the ergs and cycles spent per unit of gas by the passed calls, with percentiles,
+    /// per-directory aggregates and the worst outliers.
+    #[structopt(long = "benchmark")]
+    pub benchmark: bool,
+
+    /// Writes the gas, ergs and cycles of every passed call with their ratios to the specified
+    /// file as CSV.
+    #[structopt(long = "benchmark-csv")]
+    pub benchmark_csv: Option<PathBuf>,
+
+    /// Stores the gas, ergs and cycles of every passed call to the specified file as JSON, to be
+    /// used with `--bench-baseline` later.
+    #[structopt(long = "benchmark-json")]
+    pub benchmark_json: Option<PathBuf>,
+
+    /// Compares the ergs and cycles of every passed call against the stored benchmark, failing if
+    /// any of them grows by more than `--bench-threshold`.
+    #[structopt(long = "bench-baseline")]
+    pub bench_baseline: Option<PathBuf>,
+
+    /// The relative ergs or cycles change in percent tolerated by `--bench-baseline`.
+    #[structopt(long = "bench-threshold", default_value = "5")]
+    pub bench_threshold: f64,
+
+    /// Prints the pass rate of the tests aggregated by `directory` or by `group`.
+    #[structopt(long = "breakdown")]
+    pub breakdown: Option<evm_tester::BreakdownAxis>,
+
+    /// Sorts the breakdown by `name`, `rate`, `failures` or `total`.
+    #[structopt(long = "breakdown-order", default_value = "name")]
+    pub breakdown_order: evm_tester::BreakdownOrder,
+
+    /// Prints only the specified number of the first breakdown rows.
+    #[structopt(long = "breakdown-limit")]
+    pub breakdown_limit: Option<usize>,
+
+    /// Prints only the breakdown rows whose counters differ from the `--baseline` ones.
+    #[structopt(long = "breakdown-changed", requires = "baseline")]
+    pub breakdown_changed: bool,
+
     /// Choose between `build` to compile tests only without running, and `run` to compile and run.
     #[structopt(long = "workflow", default_value = "run")]
     pub workflow: evm_tester::Workflow,
+
+    /// The command to run instead of the test suite.
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+}
+
+///
+/// The evm tester commands.
+///
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Runs randomly generated EVM programs or mutated test cases on both the EVM emulator and
+    /// revm, saving the diverging inputs as state tests.
+    #[structopt(name = "fuzz")]
+    Fuzz {
+        /// The random generator seed. Is derived from the current time if not specified.
+        #[structopt(long = "seed")]
+        seed: Option<u64>,
+
+        /// The number of generated programs.
+        #[structopt(long = "iterations", default_value = "1000")]
+        iterations: usize,
+
+        /// The directory to save the diverging inputs to.
+        #[structopt(long = "output", default_value = "fuzz")]
+        output: PathBuf,
+
+        /// Mutates the test suite cases selected by `--path` and `--group` instead of generating
+        /// random programs.
+        #[structopt(long = "mutate")]
+        mutate: bool,
+    },
+
+    /// Traces a single case on both the EVM emulator and revm, printing the first instruction
+    /// where the EIP-3155 traces diverge.
+    ///
+    /// The steps are aligned per call frame by their call depth and program counter, so an extra
+    /// step or frame on either side is reported without a counterpart and does not shift the rest.
+    #[structopt(name = "trace-diff")]
+    TraceDiff {
+        /// The test file path, either as is or relative to the general state tests directory.
+        path: PathBuf,
+
+        /// The case label.
+        #[structopt(long = "case")]
+        case: String,
+
+        /// The number of aligned steps printed before and after the divergence.
+        #[structopt(long = "context", default_value = "5")]
+        context: usize,
+
+        /// Prints the divergence as JSON.
+        #[structopt(long = "json")]
+        json: bool,
+    },
+
+    /// Executes a single case on the environment selected by `--environment`, printing its
+    /// prestate, transaction, context, output and the complete post-state as JSON.
+    #[structopt(name = "replay")]
+    Replay {
+        /// The test file path, either as is or relative to the general state tests directory.
+        path: PathBuf,
+
+        /// The case label.
+        #[structopt(long = "case")]
+        case: String,
+    },
+
+    /// Shrinks a single case failing on the environment selected by `--environment` while it
+    /// keeps failing the same way, and saves the result as a state test.
+    #[structopt(name = "minimize")]
+    Minimize {
+        /// The test file path, either as is or relative to the general state tests directory.
+        path: PathBuf,
+
+        /// The case label.
+        #[structopt(long = "case")]
+        case: String,
+
+        /// The directory to save the minimized case to.
+        #[structopt(long = "output", default_value = "minimized")]
+        output: PathBuf,
+
+        /// The maximum number of case executions.
+        #[structopt(long = "max-runs", default_value = "2000")]
+        max_runs: usize,
+    },
 }
 
 impl Arguments {
@@ -5,10 +5,13 @@
 pub(crate) mod arguments;
 
 use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use colored::Colorize;
 
 use self::arguments::Arguments;
+use self::arguments::Command;
 
 /// The rayon worker stack size.
 const RAYON_WORKER_STACK_SIZE: usize = 16 * 1024 * 1024;
@@ -40,34 +43,286 @@ fn main_inner(arguments: Arguments) -> anyhow::Result<()> {
         .build_global()
         .expect("Thread pool configuration failure");
 
-    let summary = evm_tester::Summary::new(arguments.verbosity, arguments.quiet).wrap();
+    let summary = evm_tester::Summary::new(arguments.verbosity, arguments.quiet)
+        .with_category_filter(arguments.failure_categories)
+        .wrap();
+    let gas_report = evm_tester::GasReport::default().wrap();
 
     let filters = evm_tester::Filters::new(arguments.paths, arguments.groups);
 
     let evm_tester = evm_tester::EvmTester::new(summary.clone(), filters, arguments.workflow)?;
 
     let environment = match arguments.environment {
-        Some(environment @ evm_tester::Environment::EVMEmulator) => environment,
+        Some(environment) => environment,
         None => evm_tester::Environment::EVMEmulator,
     };
 
+    // The baselines are read before the run, so that an unreadable one fails without running the suite
+    let baseline = match arguments.baseline.as_ref() {
+        Some(path) => Some(evm_tester::Baseline::read(path.as_path())?),
+        None => None,
+    };
+    let bench_baseline = match arguments.bench_baseline.as_ref() {
+        Some(path) => Some(evm_tester::Benchmark::read(path.as_path())?),
+        None => None,
+    };
+
     let run_time_start = Instant::now();
-    println!(
-        "     {} tests with {} worker threads",
-        "Running".bright_green().bold(),
-        rayon::current_num_threads(),
-    );
+    // The commands may print machine-readable output, which must not be preceded by the banner
+    if arguments.command.is_none() {
+        println!(
+            "     {} tests with {} worker threads",
+            "Running".bright_green().bold(),
+            rayon::current_num_threads(),
+        );
+    }
+
+    match arguments.command {
+        Some(Command::Fuzz {
+            seed,
+            iterations,
+            output,
+            mutate,
+        }) => {
+            let seed = seed.unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Always valid")
+                    .as_nanos() as u64
+            });
+            println!("     {} with seed {seed}", "Fuzzing".bright_green().bold());
+
+            let eravm = evm_tester::EraVM::new(era_compiler_common::Target::EVM)?;
+            let revm = evm_tester::Revm::new();
+
+            let fuzzer = evm_tester::Fuzzer::new(seed, iterations, output.clone());
+            let saved = if mutate {
+                evm_tester.run_mutation_fuzzer(&fuzzer, eravm, revm)?
+            } else {
+                fuzzer.run(summary.clone(), eravm, revm)
+            };
+            if saved > 0 {
+                println!(
+                    "       {} {saved} diverging inputs to {}",
+                    "Saved".bright_green().bold(),
+                    output.to_string_lossy(),
+                );
+            }
+            Ok(())
+        }
+        Some(Command::TraceDiff {
+            path,
+            case,
+            context,
+            json,
+        }) => {
+            let eravm = evm_tester::EraVM::new(era_compiler_common::Target::EVM)?;
+            let revm = evm_tester::Revm::new();
+
+            let trace_diff =
+                evm_tester.trace_diff(path.as_path(), case.as_str(), eravm, revm, context)?;
+            match trace_diff {
+                Some(trace_diff) if json => {
+                    println!("{}", serde_json::to_string_pretty(&trace_diff)?)
+                }
+                Some(trace_diff) => print!("{trace_diff}"),
+                None if json => println!("null"),
+                None => println!("The traces are identical"),
+            }
+            return Ok(());
+        }
+        Some(Command::Replay { path, case }) => {
+            let replay = match environment {
+                evm_tester::Environment::EVMEmulator => {
+                    let vm = evm_tester::EraVM::new(era_compiler_common::Target::EVM)?;
+
+                    evm_tester.replay(path.as_path(), case.as_str(), vm)
+                }
+                evm_tester::Environment::Revm => {
+                    let vm = evm_tester::Revm::new();
+
+                    evm_tester.replay(path.as_path(), case.as_str(), vm)
+                }
+                evm_tester::Environment::SputnikVM => {
+                    let vm = evm_tester::SputnikVM::new();
+
+                    evm_tester.replay(path.as_path(), case.as_str(), vm)
+                }
+            }?;
+            println!("{}", serde_json::to_string_pretty(&replay)?);
+            return Ok(());
+        }
+        Some(Command::Minimize {
+            path,
+            case,
+            output,
+            max_runs,
+        }) => {
+            let minimizer = match environment {
+                evm_tester::Environment::EVMEmulator => {
+                    let vm = evm_tester::EraVM::new(era_compiler_common::Target::EVM)?;
+
+                    evm_tester.minimize(path.as_path(), case.as_str(), vm, max_runs)
+                }
+                evm_tester::Environment::Revm => {
+                    let vm = evm_tester::Revm::new();
+
+                    evm_tester.minimize(path.as_path(), case.as_str(), vm, max_runs)
+                }
+                evm_tester::Environment::SputnikVM => {
+                    let vm = evm_tester::SputnikVM::new();
+
+                    evm_tester.minimize(path.as_path(), case.as_str(), vm, max_runs)
+                }
+            }?;
+            let test_path = minimizer.write(output.as_path())?;
+
+            println!(
+                "     {} {}",
+                "Failure".bright_red().bold(),
+                minimizer.failure
+            );
+            println!(
+                "    {} {} to {} in {} runs",
+                "Minimized".bright_green().bold(),
+                minimizer.original_size,
+                minimizer.size(),
+                minimizer.runs,
+            );
+            println!(
+                "       {} {}",
+                "Saved".bright_green().bold(),
+                test_path.to_string_lossy(),
+            );
+            return Ok(());
+        }
+        None if arguments.differential => {
+            let eravm = evm_tester::EraVM::new(era_compiler_common::Target::EVM)?;
+            let revm = evm_tester::Revm::new();
+
+            evm_tester.run_differential(eravm, revm)
+        }
+        None if arguments.gas_report => {
+            let eravm = evm_tester::EraVM::new(era_compiler_common::Target::EVM)?;
+            match arguments.environment {
+                None | Some(evm_tester::Environment::Revm) => {
+                    let vm = evm_tester::Revm::new();
 
-    match environment {
-        evm_tester::Environment::EVMEmulator => {
-            let vm = evm_tester::EraVM::new(era_compiler_common::Target::EVM)?;
+                    evm_tester.run_gas_report(eravm, vm, gas_report.clone())
+                }
+                Some(evm_tester::Environment::SputnikVM) => {
+                    let vm = evm_tester::SputnikVM::new();
 
-            evm_tester.run_evm_interpreter::<evm_tester::EraVMSystemContractDeployer, true>(vm)
+                    evm_tester.run_gas_report(eravm, vm, gas_report.clone())
+                }
+                Some(evm_tester::Environment::EVMEmulator) => anyhow::bail!(
+                    "The `--gas-report` reference environment must be `Revm` or `SputnikVM`"
+                ),
+            }
+        }
+        None if arguments.gas_sweep => {
+            let eravm = evm_tester::EraVM::new(era_compiler_common::Target::EVM)?;
+            let revm = evm_tester::Revm::new();
+
+            evm_tester.run_gas_sweep(eravm, revm)
+        }
+        None => {
+            let run_options = evm_tester::RunOptions::new(
+                arguments.trace,
+                arguments.export_failures.clone(),
+                arguments.state_root,
+            );
+            match environment {
+                evm_tester::Environment::EVMEmulator => {
+                    let vm = evm_tester::EraVM::new(era_compiler_common::Target::EVM)?;
+
+                    evm_tester.run(vm, run_options)
+                }
+                evm_tester::Environment::Revm => {
+                    let vm = evm_tester::Revm::new();
+
+                    evm_tester.run(vm, run_options)
+                }
+                evm_tester::Environment::SputnikVM => {
+                    let vm = evm_tester::SputnikVM::new();
+
+                    evm_tester.run(vm, run_options)
+                }
+            }
         }
     }?;
 
     let summary = evm_tester::Summary::unwrap_arc(summary);
     print!("{summary}");
+    if !arguments.quiet {
+        print!("{}", summary.ignore_histogram());
+    }
+    if let Some(limit) = arguments.slowest {
+        print!("{}", summary.timings(limit));
+    }
+    if arguments.fingerprints {
+        print!(
+            "{}",
+            summary.failure_groups(arguments.export_failures.as_deref())
+        );
+    }
+    if let Some(path) = arguments.output_json.as_ref() {
+        summary.write_json(
+            path.as_path(),
+            arguments
+                .slowest
+                .unwrap_or(evm_tester::Summary::SLOWEST_DEFAULT_LIMIT),
+        )?;
+    }
+    if let Some(path) = arguments.junit.as_ref() {
+        summary.write_junit(path.as_path())?;
+    }
+    let mut is_bench_regressed = false;
+    if arguments.benchmark
+        || arguments.benchmark_csv.is_some()
+        || arguments.benchmark_json.is_some()
+        || bench_baseline.is_some()
+    {
+        let benchmark = evm_tester::Benchmark::new(summary.elements());
+        if arguments.benchmark {
+            print!("{benchmark}");
+        }
+        if let Some(path) = arguments.benchmark_csv.as_ref() {
+            benchmark.write_csv(path.as_path())?;
+        }
+        if let Some(path) = arguments.benchmark_json.as_ref() {
+            benchmark.write_json(path.as_path())?;
+        }
+        if let Some(bench_baseline) = bench_baseline.as_ref() {
+            let comparison = benchmark.compare(bench_baseline, arguments.bench_threshold);
+            print!("{comparison}");
+            is_bench_regressed = comparison.is_regressed();
+        }
+    }
+    if let Some(axis) = arguments.breakdown {
+        let mut breakdown = summary.breakdown(axis);
+        if arguments.breakdown_changed {
+            if let Some(baseline) = baseline.as_ref() {
+                breakdown.retain_changed(&baseline.breakdown(axis));
+            }
+        }
+        breakdown.sort(arguments.breakdown_order);
+        if let Some(limit) = arguments.breakdown_limit {
+            breakdown.truncate(limit);
+        }
+        print!("{breakdown}");
+    }
+    let is_successful = match baseline.as_ref() {
+        Some(baseline) => {
+            let comparison = baseline.compare(summary.elements());
+            print!("{comparison}");
+            !comparison.is_regressed()
+        }
+        None => summary.is_successful(),
+    };
+    if arguments.gas_report {
+        print!("{}", evm_tester::GasReport::unwrap_arc(gas_report));
+    }
     println!(
         "    {} running tests in {}m{:02}s",
         "Finished".bright_green().bold(),
@@ -75,7 +330,7 @@ fn main_inner(arguments: Arguments) -> anyhow::Result<()> {
         run_time_start.elapsed().as_secs() % 60,
     );
 
-    if !summary.is_successful() {
+    if !is_successful || is_bench_regressed {
         anyhow::bail!("");
     }
 
@@ -96,9 +351,31 @@ mod tests {
             quiet: false,
             paths: vec!["tests/solidity/simple/default.sol".to_owned()],
             groups: vec![],
+            failure_categories: vec![],
             threads: Some(1),
             environment: None,
+            differential: false,
+            gas_report: false,
+            gas_sweep: false,
+            trace: false,
+            state_root: false,
+            export_failures: None,
+            fingerprints: false,
+            slowest: None,
+            output_json: None,
+            junit: None,
+            baseline: None,
+            benchmark: false,
+            benchmark_csv: None,
+            benchmark_json: None,
+            bench_baseline: None,
+            bench_threshold: 5.0,
+            breakdown: None,
+            breakdown_order: evm_tester::BreakdownOrder::Name,
+            breakdown_limit: None,
+            breakdown_changed: false,
             workflow: evm_tester::Workflow::BuildAndRun,
+            command: None,
         };
 
         crate::main_inner(arguments).expect("Manual testing failed");
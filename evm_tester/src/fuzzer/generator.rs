@@ -0,0 +1,719 @@
+//!
+//! The structured EVM program generator.
+//!
+
+use crate::vm::opcode;
+
+use super::random::Random;
+
+///
+/// The structured EVM program generator.
+///
+/// Every generated block leaves the stack as it was, so the programs only fail on purpose,
+/// e.g. due to out-of-gas, out-of-bounds return data access or an explicit revert.
+///
+pub struct Generator<'a> {
+    /// The pseudo-random generator.
+    random: &'a mut Random,
+    /// The addresses of the contracts which can be called.
+    callees: &'a [web3::types::Address],
+    /// The remaining `CREATE` nesting depth.
+    create_depth: usize,
+    /// The bytecode being generated.
+    code: Vec<u8>,
+}
+
+impl<'a> Generator<'a> {
+    /// The maximum number of top-level blocks.
+    const BLOCKS_MAX: usize = 16;
+
+    /// The maximum number of loop body blocks.
+    const LOOP_BODY_BLOCKS_MAX: usize = 4;
+
+    /// The maximum number of loop iterations.
+    const LOOP_ITERATIONS_MAX: usize = 8;
+
+    /// The number of storage slots used, small enough for the loads to hit the stores.
+    const STORAGE_SLOTS: usize = 8;
+
+    /// The size of the memory region used by the ordinary blocks.
+    const MEMORY_SIZE: usize = 0x200;
+
+    /// The memory offset where the init code of the created contracts is placed.
+    const INIT_CODE_OFFSET: usize = 0x400;
+
+    /// The size of the init code header copying the runtime code into memory.
+    const INIT_CODE_HEADER_SIZE: usize = 11;
+
+    ///
+    /// Generates a program which can call the specified contracts.
+    ///
+    pub fn generate(
+        random: &'a mut Random,
+        callees: &'a [web3::types::Address],
+        create_depth: usize,
+    ) -> Vec<u8> {
+        let mut generator = Self {
+            random,
+            callees,
+            create_depth,
+            code: Vec::with_capacity(1024),
+        };
+
+        let blocks = generator.random.range(1, Self::BLOCKS_MAX);
+        for _ in 0..blocks {
+            generator.block(false);
+        }
+        generator.terminate();
+
+        generator.code
+    }
+
+    ///
+    /// Generates init code, which deploys the specified runtime code.
+    ///
+    pub fn init_code(random: &mut Random, runtime_code: Vec<u8>) -> Vec<u8> {
+        let mut generator = Generator {
+            random,
+            callees: &[],
+            create_depth: 0,
+            code: Vec::with_capacity(Self::INIT_CODE_HEADER_SIZE + runtime_code.len()),
+        };
+
+        if generator.random.chance(5) {
+            // EIP-3541: the deployed code must not start with `0xEF`
+            generator.code.push(opcode::PUSH1);
+            generator.code.push(0xef);
+            generator.push_usize(0);
+            generator.code.push(opcode::MSTORE8);
+            generator.push_usize(1);
+            generator.push_usize(0);
+            generator.code.push(opcode::RETURN);
+            return generator.code;
+        }
+
+        generator.code.push(opcode::PUSH2);
+        generator
+            .code
+            .extend_from_slice(&(runtime_code.len() as u16).to_be_bytes());
+        generator.code.push(opcode::DUP1);
+        generator.code.push(opcode::PUSH2);
+        generator
+            .code
+            .extend_from_slice(&(Self::INIT_CODE_HEADER_SIZE as u16).to_be_bytes());
+        generator.code.push(opcode::PUSH0);
+        generator.code.push(opcode::CODECOPY);
+        generator.code.push(opcode::PUSH0);
+        generator.code.push(if generator.random.chance(10) {
+            opcode::REVERT
+        } else {
+            opcode::RETURN
+        });
+        generator.code.extend(runtime_code);
+        generator.code
+    }
+
+    ///
+    /// Generates a stack-neutral block.
+    ///
+    fn block(&mut self, is_in_loop: bool) {
+        match self.random.below(10) {
+            0 | 1 => self.arithmetic(),
+            2 => self.environment(),
+            3 => self.memory(),
+            4 => self.storage(),
+            5 => self.log(),
+            6 if !is_in_loop => self.bounded_loop(),
+            7 if !self.callees.is_empty() => self.call(),
+            8 if !is_in_loop && self.create_depth > 0 => self.create(),
+            _ => self.arithmetic(),
+        }
+    }
+
+    ///
+    /// Generates an arithmetic, comparison or bitwise operation.
+    ///
+    fn arithmetic(&mut self) {
+        const UNARY: [u8; 2] = [opcode::ISZERO, opcode::NOT];
+        const BINARY: [u8; 21] = [
+            opcode::ADD,
+            opcode::MUL,
+            opcode::SUB,
+            opcode::DIV,
+            opcode::SDIV,
+            opcode::MOD,
+            opcode::SMOD,
+            opcode::EXP,
+            opcode::SIGNEXTEND,
+            opcode::LT,
+            opcode::GT,
+            opcode::SLT,
+            opcode::SGT,
+            opcode::EQ,
+            opcode::AND,
+            opcode::OR,
+            opcode::XOR,
+            opcode::BYTE,
+            opcode::SHL,
+            opcode::SHR,
+            opcode::SAR,
+        ];
+        const TERNARY: [u8; 2] = [opcode::ADDMOD, opcode::MULMOD];
+
+        match self.random.below(8) {
+            0 => {
+                self.push_random();
+                self.code.push(*self.random.choose(&UNARY));
+            }
+            1 => {
+                self.push_random();
+                self.push_random();
+                self.push_random();
+                self.code.push(*self.random.choose(&TERNARY));
+            }
+            _ => {
+                self.push_random();
+                self.push_random();
+                self.code.push(*self.random.choose(&BINARY));
+            }
+        }
+        self.sink();
+    }
+
+    ///
+    /// Generates an execution context query.
+    ///
+    fn environment(&mut self) {
+        const NULLARY: [u8; 18] = [
+            opcode::ADDRESS,
+            opcode::ORIGIN,
+            opcode::CALLER,
+            opcode::CALLVALUE,
+            opcode::CALLDATASIZE,
+            opcode::CODESIZE,
+            opcode::GASPRICE,
+            opcode::RETURNDATASIZE,
+            opcode::COINBASE,
+            opcode::TIMESTAMP,
+            opcode::NUMBER,
+            opcode::PREVRANDAO,
+            opcode::GASLIMIT,
+            opcode::CHAINID,
+            opcode::SELFBALANCE,
+            opcode::BASEFEE,
+            opcode::BLOBBASEFEE,
+            opcode::MSIZE,
+        ];
+        const ADDRESS_UNARY: [u8; 3] = [opcode::BALANCE, opcode::EXTCODESIZE, opcode::EXTCODEHASH];
+
+        match self.random.below(6) {
+            0 => {
+                self.push_address();
+                self.code.push(*self.random.choose(&ADDRESS_UNARY));
+            }
+            1 => {
+                let block_number = self.random.below(300);
+                self.push_usize(block_number);
+                self.code.push(opcode::BLOCKHASH);
+            }
+            2 => {
+                let offset = self.random.below(80);
+                self.push_usize(offset);
+                self.code.push(opcode::CALLDATALOAD);
+            }
+            3 => {
+                let index = self.random.below(2);
+                self.push_usize(index);
+                self.code.push(opcode::BLOBHASH);
+            }
+            _ => {
+                self.code.push(*self.random.choose(&NULLARY));
+            }
+        }
+        self.sink();
+    }
+
+    ///
+    /// Generates a memory operation.
+    ///
+    fn memory(&mut self) {
+        match self.random.below(7) {
+            0 => {
+                self.push_random();
+                self.push_memory_offset();
+                self.code.push(opcode::MSTORE8);
+            }
+            1 => {
+                self.push_memory_offset();
+                self.code.push(opcode::MLOAD);
+                self.sink();
+            }
+            2 => {
+                self.push_memory_size();
+                self.push_memory_offset();
+                self.push_memory_offset();
+                self.code.push(opcode::MCOPY);
+            }
+            3 => {
+                self.push_memory_size();
+                let offset = self.random.below(80);
+                self.push_usize(offset);
+                self.push_memory_offset();
+                self.code.push(opcode::CALLDATACOPY);
+            }
+            4 => {
+                self.push_memory_size();
+                let offset = self.random.below(64);
+                self.push_usize(offset);
+                self.push_memory_offset();
+                self.code.push(opcode::RETURNDATACOPY);
+            }
+            5 => {
+                self.push_memory_size();
+                let offset = self.random.below(64);
+                self.push_usize(offset);
+                self.push_memory_offset();
+                self.push_address();
+                self.code.push(opcode::EXTCODECOPY);
+            }
+            _ => {
+                self.push_memory_size();
+                self.push_memory_offset();
+                self.code.push(opcode::KECCAK256);
+                self.sink();
+            }
+        }
+    }
+
+    ///
+    /// Generates a persistent or transient storage operation.
+    ///
+    fn storage(&mut self) {
+        match self.random.below(4) {
+            0 => {
+                self.push_random();
+                let slot = self.random.below(Self::STORAGE_SLOTS);
+                self.push_usize(slot);
+                self.code.push(opcode::SSTORE);
+            }
+            1 => {
+                self.push_random();
+                let slot = self.random.below(Self::STORAGE_SLOTS);
+                self.push_usize(slot);
+                self.code.push(opcode::TSTORE);
+            }
+            2 => {
+                let slot = self.random.below(Self::STORAGE_SLOTS);
+                self.push_usize(slot);
+                self.code.push(opcode::SLOAD);
+                self.sink();
+            }
+            _ => {
+                let slot = self.random.below(Self::STORAGE_SLOTS);
+                self.push_usize(slot);
+                self.code.push(opcode::TLOAD);
+                self.sink();
+            }
+        }
+    }
+
+    ///
+    /// Generates an event emission.
+    ///
+    fn log(&mut self) {
+        let topics = self.random.below(5);
+        for _ in 0..topics {
+            self.push_random();
+        }
+        self.push_memory_size();
+        self.push_memory_offset();
+        self.code.push(opcode::LOG0 + (topics as u8));
+    }
+
+    ///
+    /// Generates a loop with a bounded number of iterations.
+    ///
+    fn bounded_loop(&mut self) {
+        let iterations = self.random.range(1, Self::LOOP_ITERATIONS_MAX);
+        self.push_usize(iterations);
+        let loop_start = self.code.len();
+        self.code.push(opcode::JUMPDEST);
+
+        let blocks = self.random.range(1, Self::LOOP_BODY_BLOCKS_MAX);
+        for _ in 0..blocks {
+            self.block(true);
+        }
+
+        self.push_usize(1);
+        self.code.push(opcode::SWAP1);
+        self.code.push(opcode::SUB);
+        self.code.push(opcode::DUP1);
+        self.code.push(opcode::PUSH2);
+        self.code
+            .extend_from_slice(&(loop_start as u16).to_be_bytes());
+        self.code.push(opcode::JUMPI);
+        self.code.push(opcode::POP);
+    }
+
+    ///
+    /// Generates a call to one of the callees or precompiles.
+    ///
+    fn call(&mut self) {
+        let kind = *self.random.choose(&[
+            opcode::CALL,
+            opcode::CALL,
+            opcode::STATICCALL,
+            opcode::DELEGATECALL,
+            opcode::CALLCODE,
+        ]);
+
+        self.push_memory_size();
+        self.push_memory_offset();
+        self.push_memory_size();
+        self.push_memory_offset();
+        if kind == opcode::CALL || kind == opcode::CALLCODE {
+            self.push_value();
+        }
+        if self.random.chance(10) {
+            let precompile = self.random.range(1, 10);
+            self.push_usize(precompile);
+        } else {
+            let callee = *self.random.choose(self.callees);
+            self.push_address_value(callee);
+        }
+        if self.random.chance(50) {
+            self.code.push(opcode::GAS);
+        } else {
+            let gas = self.random.range(0, 100_000);
+            self.push_usize(gas);
+        }
+        self.code.push(kind);
+        self.sink();
+    }
+
+    ///
+    /// Generates a contract creation with a generated runtime code.
+    ///
+    fn create(&mut self) {
+        let runtime_code =
+            Generator::generate(&mut *self.random, self.callees, self.create_depth - 1);
+        let init_code = Self::init_code(&mut *self.random, runtime_code);
+
+        for (index, chunk) in init_code
+            .chunks(era_compiler_common::BYTE_LENGTH_FIELD)
+            .enumerate()
+        {
+            let mut word = [0u8; era_compiler_common::BYTE_LENGTH_FIELD];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.code.push(opcode::PUSH32);
+            self.code.extend_from_slice(&word);
+            self.push_usize(
+                Self::INIT_CODE_OFFSET + index * era_compiler_common::BYTE_LENGTH_FIELD,
+            );
+            self.code.push(opcode::MSTORE);
+        }
+
+        let is_create2 = self.random.chance(50);
+        if is_create2 {
+            self.push_random();
+        }
+        self.push_usize(init_code.len());
+        self.push_usize(Self::INIT_CODE_OFFSET);
+        self.push_value();
+        self.code.push(if is_create2 {
+            opcode::CREATE2
+        } else {
+            opcode::CREATE
+        });
+        self.sink();
+    }
+
+    ///
+    /// Generates the program termination.
+    ///
+    fn terminate(&mut self) {
+        match self.random.below(20) {
+            0..=11 => {
+                self.push_memory_size();
+                self.push_memory_offset();
+                self.code.push(opcode::RETURN);
+            }
+            12..=15 => self.code.push(opcode::STOP),
+            16..=18 => {
+                self.push_memory_size();
+                self.push_memory_offset();
+                self.code.push(opcode::REVERT);
+            }
+            _ => self.code.push(opcode::INVALID),
+        }
+    }
+
+    ///
+    /// Consumes the value on top of the stack, saving it to storage or memory, or dropping it.
+    ///
+    fn sink(&mut self) {
+        match self.random.below(4) {
+            0 | 1 => {
+                let slot = self.random.below(Self::STORAGE_SLOTS);
+                self.push_usize(slot);
+                self.code.push(opcode::SSTORE);
+            }
+            2 => {
+                self.push_memory_offset();
+                self.code.push(opcode::MSTORE);
+            }
+            _ => self.code.push(opcode::POP),
+        }
+    }
+
+    ///
+    /// Pushes a random value biased towards the boundary values.
+    ///
+    fn push_random(&mut self) {
+        let value = self.random.u256();
+        self.push(value);
+    }
+
+    ///
+    /// Pushes a memory offset inside the used region.
+    ///
+    fn push_memory_offset(&mut self) {
+        let offset = self.random.below(Self::MEMORY_SIZE);
+        self.push_usize(offset);
+    }
+
+    ///
+    /// Pushes a memory size, which keeps the accessed region small.
+    ///
+    fn push_memory_size(&mut self) {
+        let size = if self.random.chance(20) {
+            0
+        } else {
+            self.random
+                .below(era_compiler_common::BYTE_LENGTH_FIELD * 3)
+        };
+        self.push_usize(size);
+    }
+
+    ///
+    /// Pushes a small value to transfer.
+    ///
+    fn push_value(&mut self) {
+        let value = if self.random.chance(50) {
+            0
+        } else {
+            self.random.range(1, 1000)
+        };
+        self.push_usize(value);
+    }
+
+    ///
+    /// Pushes the address of one of the callees, the current contract or a precompile.
+    ///
+    fn push_address(&mut self) {
+        if self.callees.is_empty() || self.random.chance(30) {
+            if self.random.chance(50) {
+                self.code.push(opcode::ADDRESS);
+            } else {
+                let precompile = self.random.range(1, 10);
+                self.push_usize(precompile);
+            }
+        } else {
+            let callee = *self.random.choose(self.callees);
+            self.push_address_value(callee);
+        }
+    }
+
+    ///
+    /// Pushes the specified address.
+    ///
+    fn push_address_value(&mut self, address: web3::types::Address) {
+        self.push(web3::types::U256::from_big_endian(address.as_bytes()));
+    }
+
+    ///
+    /// Pushes the specified value.
+    ///
+    fn push_usize(&mut self, value: usize) {
+        self.push(web3::types::U256::from(value));
+    }
+
+    ///
+    /// Pushes the specified value with the shortest `PUSH` instruction.
+    ///
+    fn push(&mut self, value: web3::types::U256) {
+        if value.is_zero() {
+            self.code.push(opcode::PUSH0);
+            return;
+        }
+
+        let mut bytes = [0u8; era_compiler_common::BYTE_LENGTH_FIELD];
+        value.to_big_endian(&mut bytes);
+        let length = era_compiler_common::BYTE_LENGTH_FIELD - (value.leading_zeros() as usize) / 8;
+        self.code.push(opcode::PUSH1 + (length as u8) - 1);
+        self.code
+            .extend_from_slice(&bytes[era_compiler_common::BYTE_LENGTH_FIELD - length..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::fuzzer::random::Random;
+    use crate::vm::opcode;
+
+    use super::Generator;
+
+    /// The number of seeds every invariant is checked for.
+    const SEEDS: u64 = 1000;
+
+    ///
+    /// Returns the number of stack items the generated instruction pops and pushes.
+    ///
+    fn stack_effect(op: u8) -> (usize, usize) {
+        match op {
+            opcode::STOP | opcode::INVALID | opcode::JUMPDEST => (0, 0),
+            opcode::PUSH0..=opcode::PUSH32 => (0, 1),
+            opcode::POP => (1, 0),
+            opcode::DUP1 => (1, 2),
+            opcode::SWAP1 => (2, 2),
+            opcode::ISZERO
+            | opcode::NOT
+            | opcode::BALANCE
+            | opcode::EXTCODESIZE
+            | opcode::EXTCODEHASH
+            | opcode::BLOCKHASH
+            | opcode::CALLDATALOAD
+            | opcode::BLOBHASH
+            | opcode::MLOAD
+            | opcode::SLOAD
+            | opcode::TLOAD => (1, 1),
+            opcode::ADDMOD | opcode::MULMOD => (3, 1),
+            opcode::ADD..=opcode::SIGNEXTEND | opcode::LT..=opcode::SAR | opcode::KECCAK256 => {
+                (2, 1)
+            }
+            opcode::ADDRESS
+            | opcode::ORIGIN
+            | opcode::CALLER
+            | opcode::CALLVALUE
+            | opcode::CALLDATASIZE
+            | opcode::CODESIZE
+            | opcode::GASPRICE
+            | opcode::RETURNDATASIZE
+            | opcode::COINBASE
+            | opcode::TIMESTAMP
+            | opcode::NUMBER
+            | opcode::PREVRANDAO
+            | opcode::GASLIMIT
+            | opcode::CHAINID
+            | opcode::SELFBALANCE
+            | opcode::BASEFEE
+            | opcode::BLOBBASEFEE
+            | opcode::MSIZE
+            | opcode::GAS => (0, 1),
+            opcode::MSTORE
+            | opcode::MSTORE8
+            | opcode::SSTORE
+            | opcode::TSTORE
+            | opcode::JUMPI
+            | opcode::RETURN
+            | opcode::REVERT => (2, 0),
+            opcode::MCOPY | opcode::CALLDATACOPY | opcode::RETURNDATACOPY | opcode::CODECOPY => {
+                (3, 0)
+            }
+            opcode::EXTCODECOPY => (4, 0),
+            op if (opcode::LOG0..=opcode::LOG0 + 4).contains(&op) => {
+                (2 + (op - opcode::LOG0) as usize, 0)
+            }
+            opcode::CREATE => (3, 1),
+            opcode::CREATE2 => (4, 1),
+            opcode::DELEGATECALL | opcode::STATICCALL => (6, 1),
+            opcode::CALL | opcode::CALLCODE => (7, 1),
+            op => panic!("Unexpected opcode {op:#04x}"),
+        }
+    }
+
+    ///
+    /// Checks that the program never underflows the stack, only jumps back to the loop starts
+    /// with the stack height they were entered with, and terminates with an empty stack.
+    ///
+    fn check_program(code: &[u8]) {
+        let mut height = 0;
+        let mut jumpdest_heights = HashMap::new();
+        let mut last_op = None;
+
+        let mut index = 0;
+        while index < code.len() {
+            let op = code[index];
+            let (pops, pushes) = stack_effect(op);
+            assert!(height >= pops, "Stack underflow at {index}");
+
+            match op {
+                opcode::JUMPDEST => {
+                    jumpdest_heights.insert(index, height);
+                }
+                opcode::JUMPI => {
+                    assert_eq!(code[index - 3], opcode::PUSH2, "Dynamic jump at {index}");
+                    let target = u16::from_be_bytes([code[index - 2], code[index - 1]]) as usize;
+                    assert_eq!(
+                        jumpdest_heights.get(&target),
+                        Some(&(height - pops)),
+                        "Unbalanced loop at {index}"
+                    );
+                }
+                _ => {}
+            }
+
+            height = height - pops + pushes;
+            last_op = Some(op);
+            index += 1;
+            if (opcode::PUSH1..=opcode::PUSH32).contains(&op) {
+                index += (op - opcode::PUSH1 + 1) as usize;
+            }
+        }
+
+        assert_eq!(index, code.len(), "Truncated push data");
+        assert_eq!(height, 0, "Stack is not empty on termination");
+        assert!(matches!(
+            last_op,
+            Some(opcode::STOP | opcode::RETURN | opcode::REVERT | opcode::INVALID)
+        ));
+    }
+
+    #[test]
+    fn stack_neutral() {
+        let callees = [web3::types::Address::from_low_u64_be(0x1000)];
+        for seed in 0..SEEDS {
+            let callees = &callees[..(seed % 2) as usize];
+            let code = Generator::generate(&mut Random::new(seed), callees, 2);
+            check_program(code.as_slice());
+        }
+    }
+
+    #[test]
+    fn deterministic_for_seed() {
+        for seed in 0..SEEDS {
+            assert_eq!(
+                Generator::generate(&mut Random::new(seed), &[], 2),
+                Generator::generate(&mut Random::new(seed), &[], 2),
+            );
+        }
+    }
+
+    #[test]
+    fn init_code_returns_runtime_code() {
+        for seed in 0..SEEDS {
+            let mut random = Random::new(seed);
+            let runtime_code = Generator::generate(&mut random, &[], 0);
+            let init_code = Generator::init_code(&mut random, runtime_code.clone());
+            if init_code[0] == opcode::PUSH2 {
+                let (header, code) = init_code.split_at(Generator::INIT_CODE_HEADER_SIZE);
+                check_program(header);
+                assert_eq!(code, runtime_code.as_slice());
+            } else {
+                check_program(init_code.as_slice());
+            }
+        }
+    }
+}
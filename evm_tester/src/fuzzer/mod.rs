@@ -0,0 +1,416 @@
+//!
+//! The differential fuzzer.
+//!
+
+pub mod generator;
+pub mod mutator;
+pub mod random;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+
+use crate::reproducer::Reproducer;
+use crate::summary::Summary;
+use crate::test::case::transaction::FieldTo;
+use crate::test::case::transaction::Transaction;
+use crate::test::case::Case;
+use crate::test::test_structure::env_section::EnvSection;
+use crate::test::test_structure::pre_state::AccountState;
+use crate::test::test_structure::pre_state::PreState;
+use crate::test::Test;
+use crate::vm::state_test_vm::StateTestVm;
+
+use self::generator::Generator;
+use self::mutator::Mutator;
+use self::random::Random;
+
+///
+/// The differential fuzzer.
+///
+/// Runs randomly generated programs or mutants of the test suite cases on the emulator and the
+/// reference EVM, saving the diverging inputs as reproducible state tests.
+///
+pub struct Fuzzer {
+    /// The random generator seed.
+    seed: u64,
+    /// The number of generated or mutated cases.
+    iterations: usize,
+    /// The directory to save the diverging inputs to.
+    output_directory: PathBuf,
+}
+
+impl Fuzzer {
+    /// The test directory the fuzzed cases are reported under.
+    const DIRECTORY: &'static str = "fuzz";
+
+    /// The transaction sender address.
+    const SENDER: &'static str = "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b";
+
+    /// The transaction sender secret key.
+    const SECRET_KEY: &'static str =
+        "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8";
+
+    /// The transaction sender balance.
+    const SENDER_BALANCE: u128 = 1_000_000_000_000_000_000_000;
+
+    /// The entry contract address.
+    const ENTRY_ADDRESS: &'static str = "0x095e7baea6a6c7c4c2dfeb977efac326af552d87";
+
+    /// The block coinbase address.
+    const COINBASE: &'static str = "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba";
+
+    /// The first callee contract address.
+    const CALLEE_ADDRESS_BASE: u64 = 0x1000;
+
+    /// The maximum number of callee contracts.
+    const CALLEES_MAX: usize = 3;
+
+    /// The `CREATE` nesting depth of the entry contract.
+    const CREATE_DEPTH: usize = 2;
+
+    /// The transaction gas price.
+    const GAS_PRICE: u64 = 10;
+
+    /// The percentage of deploy transactions.
+    const DEPLOY_PERCENT: usize = 10;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(seed: u64, iterations: usize, output_directory: PathBuf) -> Self {
+        Self {
+            seed,
+            iterations,
+            output_directory,
+        }
+    }
+
+    ///
+    /// Runs the generated cases on the emulator and the reference EVM.
+    ///
+    /// Returns the number of saved diverging inputs.
+    ///
+    pub fn run<E, R>(&self, summary: Arc<Mutex<Summary>>, emulator: E, reference: R) -> usize
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        self.run_iterations(summary, emulator, reference, |random, iteration| {
+            let name = format!("fuzz_{}_{iteration}", self.seed);
+            (name, Self::generate_case(random))
+        })
+    }
+
+    ///
+    /// Runs the mutants of the test suite cases on the emulator and the reference EVM.
+    ///
+    /// Every iteration mutates a randomly chosen case.
+    ///
+    /// Returns the number of saved diverging inputs.
+    ///
+    pub fn run_mutations<E, R>(
+        &self,
+        summary: Arc<Mutex<Summary>>,
+        tests: Vec<Test>,
+        emulator: E,
+        reference: R,
+    ) -> anyhow::Result<usize>
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let cases: Vec<(String, Case)> = tests
+            .into_iter()
+            .flat_map(|test| {
+                let name = test.name.clone();
+                test.into_enabled_cases()
+                    .into_iter()
+                    .map(move |case| (name.clone(), case))
+            })
+            .collect();
+        if cases.is_empty() {
+            anyhow::bail!("No test cases to mutate");
+        }
+
+        Ok(
+            self.run_iterations(summary, emulator, reference, |random, iteration| {
+                let (test_name, case) = random.choose(cases.as_slice());
+                let mut case = case.clone();
+                Mutator::mutate(random, &mut case);
+                let name = format!("{test_name}_mutant_{}_{iteration}", self.seed);
+                (name, case)
+            }),
+        )
+    }
+
+    ///
+    /// Runs the cases provided by `generate` on the emulator and the reference EVM.
+    ///
+    /// Every iteration has its own generator derived from the seed, so any iteration can be
+    /// repeated regardless of the number of threads.
+    ///
+    fn run_iterations<E, R, G>(
+        &self,
+        summary: Arc<Mutex<Summary>>,
+        emulator: E,
+        reference: R,
+        generate: G,
+    ) -> usize
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+        G: Fn(&mut Random, usize) -> (String, Case) + Sync,
+    {
+        let emulator = Arc::new(emulator);
+        let reference = Arc::new(reference);
+        let saved = AtomicUsize::new(0);
+
+        let _: Vec<()> = (0..self.iterations)
+            .into_par_iter()
+            .map(|iteration| {
+                let mut random = Random::new(self.iteration_seed(iteration));
+                let (name, case) = generate(&mut random, iteration);
+
+                if self.check(
+                    summary.clone(),
+                    name,
+                    case,
+                    emulator.clone(),
+                    reference.clone(),
+                ) {
+                    saved.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+            .collect();
+
+        saved.into_inner()
+    }
+
+    ///
+    /// Returns the generator seed of the iteration.
+    ///
+    /// The seed is mixed with the iteration number, so the runs with adjacent seeds do not
+    /// repeat each other's programs.
+    ///
+    fn iteration_seed(&self, iteration: usize) -> u64 {
+        Random::new(self.seed ^ (iteration as u64).wrapping_mul(Random::GOLDEN_GAMMA)).next_u64()
+    }
+
+    ///
+    /// Runs the case on the emulator and the reference EVM, saving it if they diverge.
+    ///
+    /// The reference EVM post-state is saved as the expected one, so the reproducer also fails
+    /// on the emulator in the ordinary mode.
+    ///
+    /// Returns whether the reproducer has been saved.
+    ///
+    pub fn check<E, R>(
+        &self,
+        summary: Arc<Mutex<Summary>>,
+        name: String,
+        case: Case,
+        emulator: Arc<E>,
+        reference: Arc<R>,
+    ) -> bool
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let reproducer_case = case.clone();
+        let is_diverged = case.run_differential(
+            summary,
+            E::clone_for_case(emulator, None),
+            R::clone_for_case(reference.clone(), None),
+            name.clone(),
+            Self::DIRECTORY.to_owned(),
+            None,
+        );
+        if !is_diverged {
+            return false;
+        }
+
+        let mut reference = R::clone_for_case(reference, None);
+        if reproducer_case.execute(&mut reference).is_err() {
+            return false;
+        }
+        let mut expected_state = reference.get_state();
+        // The emulator does not transfer the fees to the coinbase
+        expected_state.remove(&reproducer_case.env.current_coinbase);
+
+        let reproducer = Reproducer::new(name.clone(), &reproducer_case, expected_state);
+        match reproducer.write(self.output_directory.as_path()) {
+            Ok(_) => true,
+            Err(error) => {
+                eprintln!("Failed to save the reproducer of `{name}`: {error}");
+                false
+            }
+        }
+    }
+
+    ///
+    /// Generates a case calling a random program, which can call other random programs.
+    ///
+    fn generate_case(random: &mut Random) -> Case {
+        let mut prestate = PreState::new();
+
+        let callees_count = random.range(0, Self::CALLEES_MAX);
+        let callees: Vec<web3::types::Address> = (0..callees_count)
+            .map(|index| {
+                web3::types::Address::from_low_u64_be(Self::CALLEE_ADDRESS_BASE + index as u64)
+            })
+            .collect();
+        for (index, address) in callees.iter().enumerate() {
+            let code = Generator::generate(random, &callees[..index], 0);
+            prestate.insert(*address, Self::generate_contract(random, code));
+        }
+
+        let sender = web3::types::Address::from_str(Self::SENDER).expect("Always valid");
+        prestate.insert(
+            sender,
+            AccountState {
+                balance: web3::types::U256::from(Self::SENDER_BALANCE),
+                code: web3::types::Bytes::default(),
+                nonce: web3::types::U256::zero(),
+                storage: HashMap::new(),
+            },
+        );
+
+        let code = Generator::generate(random, callees.as_slice(), Self::CREATE_DEPTH);
+        let (to, data) = if random.chance(Self::DEPLOY_PERCENT) {
+            (None, Generator::init_code(random, code))
+        } else {
+            let entry_address =
+                web3::types::Address::from_str(Self::ENTRY_ADDRESS).expect("Always valid");
+            prestate.insert(entry_address, Self::generate_contract(random, code));
+
+            let calldata_length = random.below(80);
+            (Some(entry_address), random.bytes(calldata_length))
+        };
+
+        let value = if random.chance(50) {
+            0
+        } else {
+            random.range(1, 1_000_000)
+        };
+        let gas_limit = random.range(50_000, 3_000_000);
+        let transaction = Transaction {
+            data: web3::types::Bytes(data),
+            gas_limit: web3::types::U256::from(gas_limit),
+            gas_price: Some(web3::types::U256::from(Self::GAS_PRICE)),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            nonce: web3::types::U256::zero(),
+            secret_key: web3::types::H256::from_str(Self::SECRET_KEY).expect("Always valid"),
+            to: FieldTo(to),
+            sender: Some(sender),
+            value: web3::types::U256::from(value),
+            access_list: None,
+        };
+
+        let env = EnvSection {
+            current_coinbase: web3::types::Address::from_str(Self::COINBASE).expect("Always valid"),
+            current_difficulty: Some(web3::types::U256::from(0x20000)),
+            current_random: Some(web3::types::U256::from(0x20000)),
+            current_base_fee: Some(web3::types::U256::from(Self::GAS_PRICE)),
+            current_gas_limit: web3::types::U256::from(u32::MAX),
+            current_number: web3::types::U256::one(),
+            current_timestamp: web3::types::U256::from(1000),
+            previous_hash: None,
+        };
+
+        Case {
+            label: "0".to_owned(),
+            prestate,
+            transaction,
+            post_state: None,
+            expected_state: HashMap::new(),
+            env,
+        }
+    }
+
+    ///
+    /// Generates a contract account with the specified code and random balance and storage.
+    ///
+    fn generate_contract(random: &mut Random, code: Vec<u8>) -> AccountState {
+        let storage_size = random.below(4);
+        let storage = (0..storage_size)
+            .map(|_| {
+                let key = web3::types::U256::from(random.below(8));
+                (key, random.u256())
+            })
+            .collect();
+
+        AccountState {
+            balance: web3::types::U256::from(random.below(1_000_000)),
+            code: web3::types::Bytes(code),
+            nonce: web3::types::U256::one(),
+            storage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+
+    use crate::reproducer::Reproducer;
+
+    use super::random::Random;
+    use super::Fuzzer;
+
+    ///
+    /// Regenerates the reproducer of the iteration the way `run` saves it.
+    ///
+    fn reproducer(fuzzer: &Fuzzer, iteration: usize) -> Reproducer {
+        let case = Fuzzer::generate_case(&mut Random::new(fuzzer.iteration_seed(iteration)));
+        let name = format!("fuzz_{}_{iteration}", fuzzer.seed);
+        Reproducer::new(name, &case, HashMap::new())
+    }
+
+    #[test]
+    fn saved_seed_regenerates_input() {
+        let output_directory =
+            std::env::temp_dir().join(format!("evm-tester-fuzzer-replay-{}", std::process::id()));
+        let fuzzer = Fuzzer::new(0x5eed, 100, output_directory.clone());
+
+        for iteration in [0, 1, 57, 99] {
+            let test_path = reproducer(&fuzzer, iteration)
+                .write(output_directory.as_path())
+                .expect("Failed to save the reproducer");
+            let saved: serde_json::Value = serde_json::from_str(
+                std::fs::read_to_string(test_path)
+                    .expect("Failed to read the reproducer")
+                    .as_str(),
+            )
+            .expect("Invalid reproducer");
+
+            let replayed = Fuzzer::new(0x5eed, 1, output_directory.clone());
+            let replayed = reproducer(&replayed, iteration);
+            let replayed = serde_json::json!({ replayed.name.clone(): replayed.test });
+            assert_eq!(saved, replayed);
+        }
+
+        let _ = std::fs::remove_dir_all(output_directory);
+    }
+
+    #[test]
+    fn adjacent_seeds_do_not_overlap() {
+        let first = Fuzzer::new(1000, 1000, std::env::temp_dir());
+        let second = Fuzzer::new(1001, 1000, std::env::temp_dir());
+
+        let first_seeds: HashSet<u64> = (0..1000)
+            .map(|iteration| first.iteration_seed(iteration))
+            .collect();
+        assert_eq!(first_seeds.len(), 1000);
+        assert!((0..1000).all(|iteration| !first_seeds.contains(&second.iteration_seed(iteration))));
+    }
+}
@@ -0,0 +1,207 @@
+//!
+//! The state test case mutator.
+//!
+
+use crate::test::case::Case;
+
+use super::random::Random;
+
+///
+/// The state test case mutator.
+///
+/// Perturbs the calldata, gas limit, value and the prestate of real cases, keeping the
+/// transaction valid, so the mutants reach the same deep paths as the original cases.
+///
+pub struct Mutator;
+
+impl Mutator {
+    /// The maximum number of mutations applied to a single case.
+    const MUTATIONS_MAX: usize = 3;
+
+    /// The intrinsic gas of a transaction.
+    const INTRINSIC_GAS: usize = 21_000;
+
+    /// The maximum transferred value, small enough for any funded sender.
+    const VALUE_MAX: usize = 1_000_000;
+
+    ///
+    /// Applies random mutations to the case.
+    ///
+    pub fn mutate(random: &mut Random, case: &mut Case) {
+        let mutations = random.range(1, Self::MUTATIONS_MAX);
+        for _ in 0..mutations {
+            match random.below(5) {
+                0 => Self::mutate_calldata(random, case),
+                1 => Self::mutate_gas_limit(random, case),
+                2 => Self::mutate_value(random, case),
+                3 => Self::mutate_storage(random, case),
+                _ => Self::mutate_balance(random, case),
+            }
+        }
+    }
+
+    ///
+    /// Flips, replaces, inserts or removes calldata bytes.
+    ///
+    fn mutate_calldata(random: &mut Random, case: &mut Case) {
+        let calldata = &mut case.transaction.data.0;
+        if calldata.is_empty() {
+            let length = random.range(1, 68);
+            calldata.extend(random.bytes(length));
+            return;
+        }
+
+        let index = random.below(calldata.len());
+        match random.below(4) {
+            0 => calldata[index] ^= 1u8 << random.below(8),
+            1 => calldata[index] = random.next_u64() as u8,
+            2 => {
+                let byte = random.next_u64() as u8;
+                calldata.insert(index, byte);
+            }
+            _ => calldata.truncate(index),
+        }
+    }
+
+    ///
+    /// Changes the gas limit, keeping it above the intrinsic gas and within the block gas limit.
+    ///
+    fn mutate_gas_limit(random: &mut Random, case: &mut Case) {
+        let gas_limit = case.transaction.gas_limit.low_u64() as usize;
+        let gas_limit = match random.below(4) {
+            0 => gas_limit / 2,
+            1 => gas_limit.saturating_mul(2),
+            2 => gas_limit.saturating_sub(random.below(1000)),
+            _ => random.range(Self::INTRINSIC_GAS, gas_limit.max(Self::INTRINSIC_GAS)),
+        };
+        let block_gas_limit =
+            (case.env.current_gas_limit.low_u64() as usize).max(Self::INTRINSIC_GAS);
+        case.transaction.gas_limit =
+            web3::types::U256::from(gas_limit.clamp(Self::INTRINSIC_GAS, block_gas_limit));
+    }
+
+    ///
+    /// Changes the transferred value.
+    ///
+    fn mutate_value(random: &mut Random, case: &mut Case) {
+        let value = match random.below(3) {
+            0 => 0,
+            1 => 1,
+            _ => random.range(2, Self::VALUE_MAX),
+        };
+        case.transaction.value = web3::types::U256::from(value);
+    }
+
+    ///
+    /// Changes, adds or removes a storage slot of a random prestate account.
+    ///
+    fn mutate_storage(random: &mut Random, case: &mut Case) {
+        let address = match Self::random_account(random, case) {
+            Some(address) => address,
+            None => return,
+        };
+        let storage = &mut case
+            .prestate
+            .get_mut(&address)
+            .expect("Always exists")
+            .storage;
+
+        let mut keys: Vec<web3::types::U256> = storage.keys().copied().collect();
+        keys.sort();
+        match random.below(3) {
+            0 if !keys.is_empty() => {
+                let key = *random.choose(keys.as_slice());
+                storage.insert(key, random.u256());
+            }
+            1 if !keys.is_empty() => {
+                let key = *random.choose(keys.as_slice());
+                storage.remove(&key);
+            }
+            _ => {
+                let key = web3::types::U256::from(random.below(16));
+                storage.insert(key, random.u256());
+            }
+        }
+    }
+
+    ///
+    /// Changes the balance of a random prestate account other than the sender.
+    ///
+    fn mutate_balance(random: &mut Random, case: &mut Case) {
+        let address = match Self::random_account(random, case) {
+            Some(address) => address,
+            None => return,
+        };
+        let account = case.prestate.get_mut(&address).expect("Always exists");
+
+        account.balance = match random.below(3) {
+            0 => web3::types::U256::zero(),
+            1 => account
+                .balance
+                .saturating_add(web3::types::U256::from(random.range(1, Self::VALUE_MAX))),
+            _ => web3::types::U256::from(random.below(Self::VALUE_MAX)),
+        };
+    }
+
+    ///
+    /// Returns a random prestate account address other than the sender.
+    ///
+    fn random_account(random: &mut Random, case: &Case) -> Option<web3::types::Address> {
+        let sender = case.transaction.sender;
+        let mut addresses: Vec<web3::types::Address> = case
+            .prestate
+            .keys()
+            .filter(|address| Some(**address) != sender)
+            .copied()
+            .collect();
+        if addresses.is_empty() {
+            return None;
+        }
+        addresses.sort();
+        Some(*random.choose(addresses.as_slice()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fuzzer::random::Random;
+    use crate::fuzzer::Fuzzer;
+
+    use super::Mutator;
+
+    #[test]
+    fn mutants_are_valid() {
+        for seed in 0..1000 {
+            let mut random = Random::new(seed);
+            let original = Fuzzer::generate_case(&mut random);
+            let mut case = original.clone();
+            for _ in 0..10 {
+                Mutator::mutate(&mut random, &mut case);
+            }
+
+            let gas_limit = case.transaction.gas_limit;
+            assert!(gas_limit >= web3::types::U256::from(Mutator::INTRINSIC_GAS));
+            assert!(gas_limit <= case.env.current_gas_limit);
+            assert!(case.transaction.value <= web3::types::U256::from(Mutator::VALUE_MAX));
+            assert_eq!(case.transaction.to.0, original.transaction.to.0);
+            assert_eq!(case.transaction.nonce, original.transaction.nonce);
+
+            let mut addresses: Vec<_> = case.prestate.keys().collect();
+            let mut original_addresses: Vec<_> = original.prestate.keys().collect();
+            addresses.sort();
+            original_addresses.sort();
+            assert_eq!(addresses, original_addresses);
+
+            let sender = original.transaction.sender.expect("Always exists");
+            assert_eq!(case.transaction.sender, Some(sender));
+            assert_eq!(
+                case.prestate[&sender].balance,
+                original.prestate[&sender].balance
+            );
+            assert_eq!(
+                case.prestate[&sender].nonce,
+                original.prestate[&sender].nonce
+            );
+        }
+    }
+}
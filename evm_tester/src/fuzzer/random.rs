@@ -0,0 +1,128 @@
+//!
+//! The deterministic pseudo-random generator.
+//!
+
+///
+/// The deterministic pseudo-random generator.
+///
+/// Is a SplitMix64 generator, so the fuzzing inputs are fully reproducible from the seed.
+///
+#[derive(Debug, Clone)]
+pub struct Random {
+    /// The generator state.
+    state: u64,
+}
+
+impl Random {
+    /// The SplitMix64 state increment.
+    pub const GOLDEN_GAMMA: u64 = 0x9e3779b97f4a7c15;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    ///
+    /// Returns the next random 64-bit value.
+    ///
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(Self::GOLDEN_GAMMA);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^ (value >> 31)
+    }
+
+    ///
+    /// Returns a random value in the `[0; bound)` range.
+    ///
+    pub fn below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            return 0;
+        }
+        (self.next_u64() % (bound as u64)) as usize
+    }
+
+    ///
+    /// Returns a random value in the `[start; end]` range.
+    ///
+    pub fn range(&mut self, start: usize, end: usize) -> usize {
+        start + self.below(end - start + 1)
+    }
+
+    ///
+    /// Returns `true` with the specified probability in percent.
+    ///
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    ///
+    /// Returns a random element of the non-empty slice.
+    ///
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    ///
+    /// Returns random bytes of the specified length.
+    ///
+    pub fn bytes(&mut self, length: usize) -> Vec<u8> {
+        (0..length).map(|_| self.next_u64() as u8).collect()
+    }
+
+    ///
+    /// Returns a random 256-bit value biased towards the boundary values.
+    ///
+    pub fn u256(&mut self) -> web3::types::U256 {
+        match self.below(8) {
+            0 => web3::types::U256::zero(),
+            1 => web3::types::U256::one(),
+            2 => web3::types::U256::MAX,
+            3 => web3::types::U256::one() << 255,
+            4 => web3::types::U256::from(self.below(256)),
+            5 => web3::types::U256::MAX - web3::types::U256::from(self.below(256)),
+            6 => web3::types::U256::one() << self.below(256),
+            _ => web3::types::U256::from_big_endian(self.bytes(32).as_slice()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Random;
+
+    #[test]
+    fn deterministic_for_seed() {
+        let mut first = Random::new(42);
+        let mut second = Random::new(42);
+        for _ in 0..1000 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+        assert_eq!(first.u256(), second.u256());
+        assert_eq!(first.bytes(64), second.bytes(64));
+    }
+
+    #[test]
+    fn different_for_seeds() {
+        let mut first = Random::new(42);
+        let mut second = Random::new(43);
+        let first: Vec<u64> = (0..16).map(|_| first.next_u64()).collect();
+        let second: Vec<u64> = (0..16).map(|_| second.next_u64()).collect();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn range_bounds() {
+        let mut random = Random::new(0);
+        for _ in 0..1000 {
+            let value = random.range(3, 7);
+            assert!((3..=7).contains(&value));
+            assert!(random.below(5) < 5);
+        }
+        assert_eq!(random.below(0), 0);
+        assert_eq!(random.range(9, 9), 9);
+    }
+}
@@ -0,0 +1,155 @@
+//!
+//! The gas equivalence report.
+//!
+
+pub mod record;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use colored::Colorize;
+
+use self::record::Record;
+
+///
+/// The gas equivalence report.
+///
+/// Collects the gas used by the emulator and by the reference EVM for each case.
+///
+#[derive(Debug, Default)]
+pub struct GasReport {
+    /// The report records.
+    records: Vec<Record>,
+}
+
+///
+/// The per-directory gas equivalence aggregate.
+///
+#[derive(Debug, Default)]
+struct DirectoryAggregate {
+    /// The number of compared cases.
+    cases: usize,
+    /// The number of cases with different gas.
+    mismatches: usize,
+    /// The total gas used by the emulator.
+    emulator_gas: u128,
+    /// The total gas used by the reference EVM.
+    reference_gas: u128,
+}
+
+impl GasReport {
+    ///
+    /// Wraps data into a thread-safe shared reference.
+    ///
+    pub fn wrap(self) -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(self))
+    }
+
+    ///
+    /// Extracts the data from the thread-safe shared reference.
+    ///
+    pub fn unwrap_arc(report: Arc<Mutex<Self>>) -> Self {
+        Arc::try_unwrap(report)
+            .expect("Last shared reference")
+            .into_inner()
+            .expect("Last shared reference")
+    }
+
+    ///
+    /// Adds a record of a single case.
+    ///
+    pub fn push(
+        report: Arc<Mutex<Self>>,
+        name: String,
+        directory: String,
+        emulator_gas: u64,
+        reference_gas: u64,
+    ) {
+        let record = Record::new(name, directory, emulator_gas, reference_gas);
+        report.lock().expect("Sync").records.push(record);
+    }
+
+    ///
+    /// Returns the per-directory aggregates.
+    ///
+    fn aggregates(&self) -> BTreeMap<&str, DirectoryAggregate> {
+        let mut aggregates: BTreeMap<&str, DirectoryAggregate> = BTreeMap::new();
+        for record in self.records.iter() {
+            let aggregate = aggregates.entry(record.directory.as_str()).or_default();
+            aggregate.cases += 1;
+            if !record.is_equivalent() {
+                aggregate.mismatches += 1;
+            }
+            aggregate.emulator_gas += record.emulator_gas as u128;
+            aggregate.reference_gas += record.reference_gas as u128;
+        }
+        aggregates
+    }
+}
+
+impl std::fmt::Display for GasReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut mismatches: Vec<&Record> = self
+            .records
+            .iter()
+            .filter(|record| !record.is_equivalent())
+            .collect();
+        mismatches.sort_by(|a, b| {
+            a.directory
+                .cmp(&b.directory)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        writeln!(
+            f,
+            "{} {} of {} cases",
+            "Gas mismatches:".bright_white().bold(),
+            mismatches.len(),
+            self.records.len(),
+        )?;
+        for record in mismatches.into_iter() {
+            writeln!(
+                f,
+                "{:>10} {}/{} (emulator {}, reference {})",
+                format!("{:+}", record.delta()).bright_red(),
+                record.directory,
+                record.name,
+                record.emulator_gas,
+                record.reference_gas,
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<48} {:>8} {:>10} {:>16} {:>16} {:>9}",
+            "DIRECTORY", "CASES", "MISMATCHES", "EMULATOR GAS", "REFERENCE GAS", "DELTA",
+        )?;
+        for (directory, aggregate) in self.aggregates().into_iter() {
+            let delta = if aggregate.reference_gas == 0 {
+                0.0
+            } else {
+                ((aggregate.emulator_gas as f64) - (aggregate.reference_gas as f64)) * 100.0
+                    / (aggregate.reference_gas as f64)
+            };
+            let mismatches = if aggregate.mismatches == 0 {
+                aggregate.mismatches.to_string().green()
+            } else {
+                aggregate.mismatches.to_string().bright_red()
+            };
+            writeln!(
+                f,
+                "{:<48} {:>8} {:>10} {:>16} {:>16} {:>8.2}%",
+                directory,
+                aggregate.cases,
+                mismatches,
+                aggregate.emulator_gas,
+                aggregate.reference_gas,
+                delta,
+            )?;
+        }
+
+        Ok(())
+    }
+}
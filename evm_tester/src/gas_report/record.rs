@@ -0,0 +1,46 @@
+//!
+//! The gas equivalence report record.
+//!
+
+///
+/// The gas equivalence report record.
+///
+#[derive(Debug, Clone)]
+pub struct Record {
+    /// The case name.
+    pub name: String,
+    /// The test directory.
+    pub directory: String,
+    /// The gas used by the emulator.
+    pub emulator_gas: u64,
+    /// The gas used by the reference EVM.
+    pub reference_gas: u64,
+}
+
+impl Record {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, directory: String, emulator_gas: u64, reference_gas: u64) -> Self {
+        Self {
+            name,
+            directory,
+            emulator_gas,
+            reference_gas,
+        }
+    }
+
+    ///
+    /// Whether the emulator charged exactly the reference amount of gas.
+    ///
+    pub fn is_equivalent(&self) -> bool {
+        self.emulator_gas == self.reference_gas
+    }
+
+    ///
+    /// Returns the emulator gas excess over the reference one. Negative if the emulator charges less.
+    ///
+    pub fn delta(&self) -> i128 {
+        (self.emulator_gas as i128) - (self.reference_gas as i128)
+    }
+}
@@ -0,0 +1,181 @@
+//!
+//! The gas limit sweep.
+//!
+
+use std::sync::Arc;
+
+use era_compiler_common::EVMVersion;
+
+use crate::differential::Differential;
+use crate::test::case::Case;
+use crate::vm::execution_result::ExecutionResult;
+use crate::vm::invalid_transaction::InvalidTransaction;
+use crate::vm::state_test_vm::StateTestVm;
+
+///
+/// The gas limit sweep.
+///
+/// Reruns a case at the gas limits around the minimum passing gas of both the emulator and the
+/// reference EVM, looking for the first limit where their success or resulting state differs.
+///
+#[derive(Debug)]
+pub struct GasSweep {
+    /// The minimum gas limit the emulator succeeds with.
+    pub emulator_minimum: Option<u64>,
+    /// The minimum gas limit the reference EVM succeeds with.
+    pub reference_minimum: Option<u64>,
+    /// The first diverging gas limit with the divergences.
+    pub divergence: Option<(u64, Differential)>,
+}
+
+impl GasSweep {
+    /// The intrinsic gas of a transaction, which is the lower bound of the search.
+    pub const INTRINSIC_GAS: u64 = 21_000;
+
+    /// The offsets from the minimum passing gas which are checked.
+    pub const OFFSETS: [i64; 11] = [-64, -16, -3, -2, -1, 0, 1, 2, 3, 16, 64];
+
+    ///
+    /// Sweeps the gas limit of the case.
+    ///
+    /// Returns `None` if the case is not supported by the emulator.
+    ///
+    pub fn run<E, R>(
+        case: &Case,
+        emulator: Arc<E>,
+        reference: Arc<R>,
+        evm_version: Option<EVMVersion>,
+    ) -> anyhow::Result<Option<Self>>
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let maximum = case.transaction.gas_limit.low_u64();
+
+        let emulator_minimum = match Self::minimum_passing_gas(case, maximum, |case| {
+            let mut vm = E::clone_for_case(emulator.clone(), evm_version);
+            case.execute(&mut vm)
+        })? {
+            Ok(minimum) => minimum,
+            Err(()) => return Ok(None),
+        };
+        let reference_minimum = Self::minimum_passing_gas(case, maximum, |case| {
+            let mut vm = R::clone_for_case(reference.clone(), evm_version);
+            case.execute(&mut vm)
+        })?
+        .unwrap_or_default();
+
+        let mut gas_limits: Vec<u64> = [emulator_minimum, reference_minimum]
+            .into_iter()
+            .flatten()
+            .flat_map(|minimum| {
+                Self::OFFSETS
+                    .iter()
+                    .map(move |offset| minimum.saturating_add_signed(*offset))
+            })
+            .filter(|gas_limit| (Self::INTRINSIC_GAS..=maximum).contains(gas_limit))
+            .collect();
+        gas_limits.sort_unstable();
+        gas_limits.dedup();
+
+        let coinbase = case.env.current_coinbase;
+        let sender = case.transaction.sender.unwrap_or_default();
+        let mut divergence = None;
+        for gas_limit in gas_limits.into_iter() {
+            let case = Self::with_gas_limit(case, gas_limit);
+
+            let mut emulator = E::clone_for_case(emulator.clone(), evm_version);
+            let emulator_result = case.execute(&mut emulator)?;
+            if emulator_result.output.system_error.is_some() {
+                return Ok(None);
+            }
+            let mut reference = R::clone_for_case(reference.clone(), evm_version);
+            let reference_result = match case.execute(&mut reference) {
+                Ok(result) => result,
+                // Nothing is executed at the limits the reference EVM rejects
+                Err(error) if error.is::<InvalidTransaction>() => continue,
+                Err(error) => return Err(error),
+            };
+
+            // The gas used and the sender balance differ at every limit if the gas accounting
+            // differs, which is reported by the gas equivalence report instead
+            let mut differential = Differential::compare(
+                &emulator_result,
+                &emulator.get_state(),
+                &reference_result,
+                &reference.get_state(),
+                case.deployed_address(),
+                &[coinbase, sender],
+            );
+            differential
+                .divergences
+                .retain(|divergence| divergence.subject != Differential::SUBJECT_GAS_USED);
+
+            if !differential.is_empty() {
+                divergence = Some((gas_limit, differential));
+                break;
+            }
+        }
+
+        Ok(Some(Self {
+            emulator_minimum,
+            reference_minimum,
+            divergence,
+        }))
+    }
+
+    ///
+    /// Finds the minimum gas limit the case succeeds with using binary search.
+    ///
+    /// Returns `Err(())` if the case is not supported by the VM, and `Ok(None)` if the case does
+    /// not succeed even with the maximum gas limit.
+    ///
+    fn minimum_passing_gas<F>(
+        case: &Case,
+        maximum: u64,
+        execute: F,
+    ) -> anyhow::Result<Result<Option<u64>, ()>>
+    where
+        F: Fn(&Case) -> anyhow::Result<ExecutionResult>,
+    {
+        let is_successful = |gas_limit: u64| -> anyhow::Result<Result<bool, ()>> {
+            let result = match execute(&Self::with_gas_limit(case, gas_limit)) {
+                Ok(result) => result,
+                Err(error) if error.is::<InvalidTransaction>() => return Ok(Ok(false)),
+                Err(error) => return Err(error),
+            };
+            if result.output.system_error.is_some() {
+                return Ok(Err(()));
+            }
+            Ok(Ok(!result.output.exception))
+        };
+
+        match is_successful(maximum)? {
+            Ok(true) => {}
+            Ok(false) => return Ok(Ok(None)),
+            Err(()) => return Ok(Err(())),
+        }
+
+        let mut low = Self::INTRINSIC_GAS.min(maximum);
+        let mut high = maximum;
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match is_successful(middle)? {
+                Ok(true) => high = middle,
+                Ok(false) => low = middle + 1,
+                Err(()) => return Ok(Err(())),
+            }
+        }
+
+        Ok(Ok(Some(high)))
+    }
+
+    ///
+    /// Returns a copy of the case with the specified gas limit.
+    ///
+    fn with_gas_limit(case: &Case, gas_limit: u64) -> Case {
+        let mut case = case.clone();
+        case.transaction.gas_limit = web3::types::U256::from(gas_limit);
+        case
+    }
+}
@@ -7,11 +7,21 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::type_complexity)]
 
+pub(crate) mod baseline;
+pub(crate) mod benchmark;
+pub(crate) mod differential;
 pub(crate) mod environment;
 pub(crate) mod filters;
+pub(crate) mod fuzzer;
+pub(crate) mod gas_report;
+pub(crate) mod gas_sweep;
+pub(crate) mod minimizer;
+pub(crate) mod replay;
+pub(crate) mod reproducer;
 pub(crate) mod summary;
 pub(crate) mod test;
 pub(crate) mod test_suits;
+pub(crate) mod trace;
 pub(crate) mod utils;
 pub(crate) mod vm;
 pub(crate) mod workflow;
@@ -24,15 +34,29 @@ use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use test::Test;
 
+pub use crate::baseline::Baseline;
+pub use crate::benchmark::Benchmark;
 pub use crate::environment::Environment;
 pub use crate::filters::Filters;
+pub use crate::fuzzer::Fuzzer;
+pub use crate::gas_report::GasReport;
+pub use crate::minimizer::Minimizer;
+pub use crate::replay::Replay;
+pub use crate::summary::breakdown::axis::Axis as BreakdownAxis;
+pub use crate::summary::breakdown::order::Order as BreakdownOrder;
+pub use crate::summary::element::outcome::category::Category as FailureCategory;
 pub use crate::summary::Summary;
+pub use crate::test::run_options::RunOptions;
 pub use crate::test_suits::ethereum_general_state::EthereumGeneralStateTestsDirectory;
 pub use crate::test_suits::Collection;
+pub use crate::trace::trace_diff::TraceDiff;
 pub use crate::vm::eravm::deployers::dummy_deployer::DummyDeployer as EraVMNativeDeployer;
 pub use crate::vm::eravm::deployers::system_contract_deployer::SystemContractDeployer as EraVMSystemContractDeployer;
 pub use crate::vm::eravm::deployers::EraVMDeployer;
 pub use crate::vm::eravm::EraVM;
+pub use crate::vm::revm::Revm;
+pub use crate::vm::sputnikvm::SputnikVM;
+pub use crate::vm::state_test_vm::StateTestVm;
 pub use crate::workflow::Workflow;
 
 ///
@@ -70,25 +94,194 @@ impl EvmTester {
     }
 
     ///
-    /// Runs all tests on EVM interpreter.
+    /// Runs all tests on the specified VM.
     ///
-    pub fn run_evm_interpreter<D, const M: bool>(self, vm: EraVM) -> anyhow::Result<()>
+    pub fn run<V>(self, vm: V, options: RunOptions) -> anyhow::Result<()>
     where
-        D: EraVMDeployer,
+        V: StateTestVm,
     {
-        let tests = self.all_tests()?;
         let vm = Arc::new(vm);
+        self.run_tests(|test| test.run(self.summary.clone(), vm.clone(), &options))
+    }
+
+    ///
+    /// Runs all tests on both the emulator and the reference EVM, reporting any divergence
+    /// in the resulting states.
+    ///
+    pub fn run_differential<E, R>(self, emulator: E, reference: R) -> anyhow::Result<()>
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let emulator = Arc::new(emulator);
+        let reference = Arc::new(reference);
+        self.run_tests(|test| {
+            test.run_differential(self.summary.clone(), emulator.clone(), reference.clone())
+        })
+    }
+
+    ///
+    /// Runs all tests on both the emulator and the reference EVM, recording the gas used
+    /// by both into the gas equivalence report.
+    ///
+    pub fn run_gas_report<E, R>(
+        self,
+        emulator: E,
+        reference: R,
+        gas_report: Arc<Mutex<GasReport>>,
+    ) -> anyhow::Result<()>
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let emulator = Arc::new(emulator);
+        let reference = Arc::new(reference);
+        self.run_tests(|test| {
+            test.run_gas_report(
+                self.summary.clone(),
+                gas_report.clone(),
+                emulator.clone(),
+                reference.clone(),
+            )
+        })
+    }
+
+    ///
+    /// Runs all tests on both the emulator and the reference EVM at the gas limits around the
+    /// minimum passing gas, reporting the first limit where they diverge.
+    ///
+    pub fn run_gas_sweep<E, R>(self, emulator: E, reference: R) -> anyhow::Result<()>
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let emulator = Arc::new(emulator);
+        let reference = Arc::new(reference);
+        self.run_tests(|test| {
+            test.run_gas_sweep(self.summary.clone(), emulator.clone(), reference.clone())
+        })
+    }
+
+    ///
+    /// Runs the mutants of all test cases on both the emulator and the reference EVM.
+    ///
+    /// Returns the number of saved diverging inputs.
+    ///
+    pub fn run_mutation_fuzzer<E, R>(
+        self,
+        fuzzer: &Fuzzer,
+        emulator: E,
+        reference: R,
+    ) -> anyhow::Result<usize>
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let tests = self.all_tests()?;
+
+        fuzzer.run_mutations(self.summary.clone(), tests, emulator, reference)
+    }
+
+    ///
+    /// Traces a single case on both the emulator and the reference EVM, returning the first
+    /// divergence between the traces with `context` steps around it.
+    ///
+    pub fn trace_diff<E, R>(
+        &self,
+        test_path: &Path,
+        label: &str,
+        emulator: E,
+        reference: R,
+        context: usize,
+    ) -> anyhow::Result<Option<TraceDiff>>
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let test = self.read_test(test_path)?;
+        let name = format!("{}: {label}", test.name);
+        let case = test.into_case(label)?;
+
+        let mut emulator = E::clone_for_case(Arc::new(emulator), None);
+        let (_, emulator_trace) = case.execute_traced(&mut emulator, name.clone())?;
+        if emulator_trace.steps.is_empty() {
+            anyhow::bail!(
+                "No EVM steps have been traced on the emulator for `{name}`. The emulator must be built with step logging"
+            );
+        }
+        let mut reference = R::clone_for_case(Arc::new(reference), None);
+        let (_, reference_trace) = case.execute_traced(&mut reference, name)?;
+
+        Ok(TraceDiff::compare(
+            &emulator_trace,
+            &reference_trace,
+            context,
+        ))
+    }
+
+    ///
+    /// Executes a single case on the specified VM, returning the complete dump of its inputs
+    /// and results.
+    ///
+    pub fn replay<V>(&self, test_path: &Path, label: &str, vm: V) -> anyhow::Result<Replay>
+    where
+        V: StateTestVm,
+    {
+        let test = self.read_test(test_path)?;
+        let name = format!("{}: {label}", test.name);
+        let case = test.into_case(label)?;
+
+        let vm = V::clone_for_case(Arc::new(vm), None);
+        Replay::run(name, case, vm)
+    }
+
+    ///
+    /// Shrinks a single failing case on the specified VM while it keeps failing the same way,
+    /// executing it at most `max_runs` times.
+    ///
+    pub fn minimize<V>(
+        &self,
+        test_path: &Path,
+        label: &str,
+        vm: V,
+        max_runs: usize,
+    ) -> anyhow::Result<Minimizer>
+    where
+        V: StateTestVm,
+    {
+        let test = self.read_test(test_path)?;
+        let name = format!("{}: {label}", test.name);
+        let case = test.into_case(label)?;
+
+        Minimizer::run(name, case, vm, max_runs)
+    }
+
+    ///
+    /// Reads all tests, starts the progress display and runs `run_test` on each test in parallel.
+    ///
+    fn run_tests<F>(&self, run_test: F) -> anyhow::Result<()>
+    where
+        F: Fn(Test) + Sync + Send,
+    {
+        let tests = self.all_tests()?;
+        self.start_progress(tests.as_slice());
 
-        let _: Vec<()> = tests
-            .into_par_iter()
-            .map(|test| {
-                test.run_evm_interpreter::<D, M>(self.summary.clone(), vm.clone());
-            })
-            .collect();
+        tests.into_par_iter().for_each(run_test);
 
         Ok(())
     }
 
+    ///
+    /// Reads a single test file of the general state tests.
+    ///
+    fn read_test(&self, test_path: &Path) -> anyhow::Result<Test> {
+        EthereumGeneralStateTestsDirectory::read_test(
+            test_path,
+            Path::new(Self::GENERAL_STATE_TESTS),
+            Path::new(Self::GENERAL_STATE_TESTS_FILLER),
+        )
+    }
+
     ///
     /// Returns all tests from all directories.
     ///
@@ -103,6 +296,14 @@ impl EvmTester {
         Ok(tests)
     }
 
+    ///
+    /// Starts displaying the progress of the cases of the tests.
+    ///
+    fn start_progress(&self, tests: &[Test]) {
+        let total = tests.iter().map(|test| test.cases.len()).sum();
+        self.summary.lock().expect("Sync").start_progress(total);
+    }
+
     ///
     /// Returns all tests from the specified directory.
     ///
@@ -0,0 +1,70 @@
+//!
+//! The minimized case size.
+//!
+
+use crate::test::case::Case;
+use crate::vm::opcode;
+
+///
+/// The size of a case along every dimension the minimizer reduces.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaseSize {
+    /// The number of prestate accounts.
+    pub accounts: usize,
+    /// The number of prestate storage slots.
+    pub storage_slots: usize,
+    /// The calldata length.
+    pub calldata_length: usize,
+    /// The number of prestate code instructions which are not `JUMPDEST`.
+    pub instructions: usize,
+    /// The number of expected post-state accounts.
+    pub expectations: usize,
+}
+
+impl CaseSize {
+    ///
+    /// Measures the case.
+    ///
+    pub fn new(case: &Case) -> Self {
+        let accounts = case.prestate.len();
+        let storage_slots = case
+            .prestate
+            .values()
+            .map(|account| account.storage.len())
+            .sum();
+        let instructions = case
+            .prestate
+            .values()
+            .map(|account| {
+                let code = account.code.0.as_slice();
+                opcode::instructions(code)
+                    .into_iter()
+                    .filter(|(offset, _)| code[*offset] != opcode::JUMPDEST)
+                    .count()
+            })
+            .sum();
+
+        Self {
+            accounts,
+            storage_slots,
+            calldata_length: case.transaction.data.0.len(),
+            instructions,
+            expectations: case.expected_state.len(),
+        }
+    }
+}
+
+impl std::fmt::Display for CaseSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} accounts, {} storage slots, {} calldata bytes, {} instructions, {} expected accounts",
+            self.accounts,
+            self.storage_slots,
+            self.calldata_length,
+            self.instructions,
+            self.expectations,
+        )
+    }
+}
@@ -0,0 +1,559 @@
+//!
+//! The failing case minimizer.
+//!
+
+pub mod case_size;
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::reproducer::Reproducer;
+use crate::test::case::Case;
+use crate::utils;
+use crate::vm::opcode;
+use crate::vm::state_test_vm::StateTestVm;
+
+use self::case_size::CaseSize;
+
+///
+/// The failing case minimizer.
+///
+/// Shrinks a case with delta debugging while it keeps failing the same way, that is with the
+/// same expected value of the first mismatching expectation or the same execution error. The
+/// prestate accounts, storage slots, expected accounts and code instructions are removed in
+/// halving chunks, and the calldata is truncated, until no reduction keeps the failure or the
+/// run budget is exhausted.
+///
+#[derive(Debug)]
+pub struct Minimizer {
+    /// The full case name.
+    pub name: String,
+    /// The minimized case.
+    pub case: Case,
+    /// The preserved failure.
+    pub failure: String,
+    /// The size of the original case.
+    pub original_size: CaseSize,
+    /// The number of executed runs.
+    pub runs: usize,
+    /// The maximum number of runs.
+    max_runs: usize,
+}
+
+impl Minimizer {
+    ///
+    /// Minimizes the failing case on the specified VM, executing it at most `max_runs` times.
+    ///
+    pub fn run<V>(name: String, case: Case, vm: V, max_runs: usize) -> anyhow::Result<Self>
+    where
+        V: StateTestVm,
+    {
+        let vm = Arc::new(vm);
+
+        Self::run_with(name, case, max_runs, |case| {
+            case.failure(&mut V::clone_for_case(vm.clone(), None))
+        })
+    }
+
+    ///
+    /// Minimizes the case failing as described by `failure`, which returns `None` for the passing
+    /// cases, calling it at most `max_runs` times.
+    ///
+    pub fn run_with<F>(
+        name: String,
+        case: Case,
+        max_runs: usize,
+        failure: F,
+    ) -> anyhow::Result<Self>
+    where
+        F: Fn(&Case) -> Option<String>,
+    {
+        let original_failure = match failure(&case) {
+            Some(failure) => failure,
+            None => anyhow::bail!("The case `{name}` does not fail on the selected environment"),
+        };
+
+        let mut minimizer = Self {
+            name,
+            original_size: CaseSize::new(&case),
+            case,
+            failure: original_failure,
+            runs: 1,
+            max_runs,
+        };
+
+        loop {
+            let size = CaseSize::new(&minimizer.case);
+
+            minimizer.reduce_accounts(&failure);
+            minimizer.reduce_expectations(&failure);
+            minimizer.reduce_storage(&failure);
+            minimizer.reduce_calldata(&failure);
+            minimizer.reduce_code(&failure);
+
+            if CaseSize::new(&minimizer.case) == size || minimizer.runs >= minimizer.max_runs {
+                break;
+            }
+        }
+
+        Ok(minimizer)
+    }
+
+    ///
+    /// Returns the size of the minimized case.
+    ///
+    pub fn size(&self) -> CaseSize {
+        CaseSize::new(&self.case)
+    }
+
+    ///
+    /// Writes the minimized case as a state test with its filler to the specified directory.
+    ///
+    /// Returns the state test file path.
+    ///
+    pub fn write(&self, directory: &Path) -> anyhow::Result<PathBuf> {
+        let name = format!("{}_minimized", utils::file_name(self.name.as_str()));
+        Reproducer::from_expectations(name, &self.case).write(directory)
+    }
+
+    ///
+    /// Removes the prestate accounts, except for the transaction sender.
+    ///
+    fn reduce_accounts<F>(&mut self, failure: &F)
+    where
+        F: Fn(&Case) -> Option<String>,
+    {
+        let sender = self.case.transaction.sender;
+        let mut addresses: Vec<web3::types::Address> = self
+            .case
+            .prestate
+            .keys()
+            .copied()
+            .filter(|address| Some(*address) != sender)
+            .collect();
+        addresses.sort_unstable();
+
+        let case = self.case.clone();
+        self.case = self.reduce(failure, addresses, |kept| {
+            let mut case = case.clone();
+            case.prestate
+                .retain(|address, _| Some(*address) == sender || kept.contains(address));
+            case
+        });
+    }
+
+    ///
+    /// Removes the expected post-state accounts which are not needed to detect the failure.
+    ///
+    fn reduce_expectations<F>(&mut self, failure: &F)
+    where
+        F: Fn(&Case) -> Option<String>,
+    {
+        let mut addresses: Vec<web3::types::Address> =
+            self.case.expected_state.keys().copied().collect();
+        addresses.sort_unstable();
+
+        let case = self.case.clone();
+        self.case = self.reduce(failure, addresses, |kept| {
+            let mut case = case.clone();
+            case.expected_state
+                .retain(|address, _| kept.contains(address));
+            case
+        });
+    }
+
+    ///
+    /// Removes the prestate storage slots.
+    ///
+    fn reduce_storage<F>(&mut self, failure: &F)
+    where
+        F: Fn(&Case) -> Option<String>,
+    {
+        let mut slots: Vec<(web3::types::Address, web3::types::U256)> = self
+            .case
+            .prestate
+            .iter()
+            .flat_map(|(address, account)| account.storage.keys().map(|key| (*address, *key)))
+            .collect();
+        slots.sort_unstable();
+
+        let case = self.case.clone();
+        self.case = self.reduce(failure, slots, |kept| {
+            let mut case = case.clone();
+            for (address, account) in case.prestate.iter_mut() {
+                account
+                    .storage
+                    .retain(|key, _| kept.contains(&(*address, *key)));
+            }
+            case
+        });
+    }
+
+    ///
+    /// Truncates the calldata to the shortest prefix keeping the failure.
+    ///
+    fn reduce_calldata<F>(&mut self, failure: &F)
+    where
+        F: Fn(&Case) -> Option<String>,
+    {
+        let mut length = self.case.transaction.data.0.len();
+        let mut step = length;
+        while step > 0 {
+            let mut case = self.case.clone();
+            case.transaction.data.0.truncate(length - step);
+            if self.is_failing(failure, &case) {
+                self.case = case;
+                length -= step;
+                step = step.min(length);
+            } else {
+                step /= 2;
+            }
+        }
+    }
+
+    ///
+    /// Replaces the code instructions of every prestate account with `JUMPDEST`, which is the
+    /// cheapest instruction without side effects.
+    ///
+    /// The push data is replaced together with the push instruction.
+    ///
+    fn reduce_code<F>(&mut self, failure: &F)
+    where
+        F: Fn(&Case) -> Option<String>,
+    {
+        let mut addresses: Vec<web3::types::Address> = self.case.prestate.keys().copied().collect();
+        addresses.sort_unstable();
+
+        for address in addresses.into_iter() {
+            let code = self.case.prestate[&address].code.0.clone();
+            let instructions: Vec<(usize, usize)> = opcode::instructions(code.as_slice())
+                .into_iter()
+                .filter(|(offset, _)| code[*offset] != opcode::JUMPDEST)
+                .collect();
+
+            let case = self.case.clone();
+            self.case = self.reduce(failure, instructions.clone(), |kept| {
+                let mut case = case.clone();
+                let code = &mut case
+                    .prestate
+                    .get_mut(&address)
+                    .expect("Always exists")
+                    .code
+                    .0;
+                for (offset, size) in instructions.iter() {
+                    if !kept.contains(&(*offset, *size)) {
+                        code[*offset..*offset + *size].fill(opcode::JUMPDEST);
+                    }
+                }
+                case
+            });
+        }
+    }
+
+    ///
+    /// Removes the candidates in halving chunks while the case built from the kept ones keeps
+    /// failing the same way.
+    ///
+    /// Returns the case built from the kept candidates.
+    ///
+    fn reduce<F, T, B>(&mut self, failure: &F, candidates: Vec<T>, build: B) -> Case
+    where
+        F: Fn(&Case) -> Option<String>,
+        T: Clone,
+        B: Fn(&[T]) -> Case,
+    {
+        let mut kept = candidates;
+        let mut chunk_size = (kept.len() / 2).max(1);
+
+        while !kept.is_empty() && self.runs < self.max_runs {
+            let mut is_reduced = false;
+            let mut start = 0;
+            while start < kept.len() {
+                let end = (start + chunk_size).min(kept.len());
+                let candidate: Vec<T> = kept[..start]
+                    .iter()
+                    .chain(kept[end..].iter())
+                    .cloned()
+                    .collect();
+                if self.is_failing(failure, &build(candidate.as_slice())) {
+                    kept = candidate;
+                    is_reduced = true;
+                } else {
+                    start = end;
+                }
+            }
+
+            if chunk_size > 1 {
+                chunk_size = (chunk_size / 2).max(1);
+            } else if !is_reduced {
+                break;
+            }
+        }
+
+        build(kept.as_slice())
+    }
+
+    ///
+    /// Whether the case fails the same way as the original one.
+    ///
+    /// Returns `false` without running the case if the run budget is exhausted.
+    ///
+    fn is_failing<F>(&mut self, failure: &F, case: &Case) -> bool
+    where
+        F: Fn(&Case) -> Option<String>,
+    {
+        if self.runs >= self.max_runs {
+            return false;
+        }
+        self.runs += 1;
+
+        failure(case).as_deref() == Some(self.failure.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::collections::HashMap;
+
+    use crate::test::case::transaction::FieldTo;
+    use crate::test::case::transaction::Transaction;
+    use crate::test::case::Case;
+    use crate::test::filler_structure::AccountFillerStruct;
+    use crate::test::test_structure::env_section::EnvSection;
+    use crate::test::test_structure::pre_state::AccountState;
+    use crate::vm::opcode;
+
+    use super::Minimizer;
+
+    /// The transaction sender, which is never removed.
+    const SENDER: u64 = 0xa94f;
+
+    /// The account whose code and storage slot are needed for the failure.
+    const FAILING: u64 = 0x1000;
+
+    /// The account which must be present for the failure.
+    const NEEDED: u64 = 0x2000;
+
+    /// The account which is irrelevant to the failure.
+    const IRRELEVANT: u64 = 0x3000;
+
+    /// The storage slot needed for the failure.
+    const NEEDED_SLOT: u64 = 2;
+
+    /// The calldata length needed for the failure.
+    const NEEDED_CALLDATA_LENGTH: usize = 3;
+
+    ///
+    /// Returns the account with some code and storage.
+    ///
+    fn account(slots: &[u64]) -> AccountState {
+        AccountState {
+            balance: web3::types::U256::from(1000),
+            code: web3::types::Bytes(vec![
+                opcode::PUSH1,
+                0x01,
+                opcode::PUSH1,
+                0x02,
+                opcode::SSTORE,
+                opcode::CALLER,
+                opcode::POP,
+                opcode::STOP,
+            ]),
+            nonce: web3::types::U256::one(),
+            storage: slots
+                .iter()
+                .map(|slot| {
+                    (
+                        web3::types::U256::from(*slot),
+                        web3::types::U256::from(*slot),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    ///
+    /// Returns the case to minimize.
+    ///
+    fn case() -> Case {
+        let address = web3::types::Address::from_low_u64_be;
+
+        let prestate = HashMap::from([
+            (address(SENDER), account(&[])),
+            (address(FAILING), account(&[1, NEEDED_SLOT, 3, 4])),
+            (address(NEEDED), account(&[5])),
+            (address(IRRELEVANT), account(&[6, 7])),
+        ]);
+        let expected_state = [FAILING, NEEDED, IRRELEVANT]
+            .into_iter()
+            .map(|value| (address(value), AccountFillerStruct::default()))
+            .collect();
+
+        Case {
+            label: "0".to_owned(),
+            prestate,
+            transaction: Transaction {
+                data: web3::types::Bytes((0..16).collect()),
+                gas_limit: web3::types::U256::from(100_000),
+                gas_price: Some(web3::types::U256::from(10)),
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                nonce: web3::types::U256::zero(),
+                secret_key: web3::types::H256::zero(),
+                to: FieldTo(Some(address(FAILING))),
+                sender: Some(address(SENDER)),
+                value: web3::types::U256::zero(),
+                access_list: None,
+            },
+            post_state: None,
+            expected_state,
+            env: EnvSection {
+                current_coinbase: address(0xc0),
+                current_difficulty: None,
+                current_random: None,
+                current_base_fee: None,
+                current_gas_limit: web3::types::U256::from(u32::MAX),
+                current_number: web3::types::U256::one(),
+                current_timestamp: web3::types::U256::from(1000),
+                previous_hash: None,
+            },
+        }
+    }
+
+    ///
+    /// The synthetic failure, which needs one storage slot and the `SSTORE` instruction of the
+    /// failing account, the presence of the needed account, one expected account and a calldata
+    /// prefix.
+    ///
+    fn failure(case: &Case) -> Option<String> {
+        let address = web3::types::Address::from_low_u64_be;
+
+        let account = case.prestate.get(&address(FAILING))?;
+        let code = account.code.0.as_slice();
+        let is_failing = account
+            .storage
+            .contains_key(&web3::types::U256::from(NEEDED_SLOT))
+            && opcode::instructions(code)
+                .into_iter()
+                .any(|(offset, _)| code[offset] == opcode::SSTORE)
+            && case.prestate.contains_key(&address(NEEDED))
+            && case.expected_state.contains_key(&address(NEEDED))
+            && case.transaction.data.0.len() >= NEEDED_CALLDATA_LENGTH;
+        is_failing.then(|| "synthetic".to_owned())
+    }
+
+    ///
+    /// Returns every case with a single element removed along any of the minimized dimensions.
+    ///
+    fn single_reductions(case: &Case) -> Vec<Case> {
+        let mut reductions = Vec::new();
+
+        for address in case.prestate.keys() {
+            if Some(*address) == case.transaction.sender {
+                continue;
+            }
+            let mut reduction = case.clone();
+            reduction.prestate.remove(address);
+            reductions.push(reduction);
+
+            for key in case.prestate[address].storage.keys() {
+                let mut reduction = case.clone();
+                reduction
+                    .prestate
+                    .get_mut(address)
+                    .expect("Always exists")
+                    .storage
+                    .remove(key);
+                reductions.push(reduction);
+            }
+
+            let code = case.prestate[address].code.0.as_slice();
+            for (offset, size) in opcode::instructions(code) {
+                if code[offset] == opcode::JUMPDEST {
+                    continue;
+                }
+                let mut reduction = case.clone();
+                reduction
+                    .prestate
+                    .get_mut(address)
+                    .expect("Always exists")
+                    .code
+                    .0[offset..offset + size]
+                    .fill(opcode::JUMPDEST);
+                reductions.push(reduction);
+            }
+        }
+
+        for address in case.expected_state.keys() {
+            let mut reduction = case.clone();
+            reduction.expected_state.remove(address);
+            reductions.push(reduction);
+        }
+
+        if !case.transaction.data.0.is_empty() {
+            let mut reduction = case.clone();
+            reduction.transaction.data.0.pop();
+            reductions.push(reduction);
+        }
+
+        reductions
+    }
+
+    #[test]
+    fn one_minimal() {
+        let minimizer = Minimizer::run_with("test".to_owned(), case(), 10_000, failure)
+            .expect("The case must fail");
+
+        assert_eq!(failure(&minimizer.case).as_deref(), Some("synthetic"));
+        for reduction in single_reductions(&minimizer.case) {
+            assert_eq!(failure(&reduction), None, "{reduction:?} still fails");
+        }
+
+        let size = minimizer.size();
+        assert_eq!(size.accounts, 3);
+        assert_eq!(size.storage_slots, 1);
+        assert_eq!(size.calldata_length, NEEDED_CALLDATA_LENGTH);
+        assert_eq!(size.instructions, 1);
+        assert_eq!(size.expectations, 1);
+    }
+
+    #[test]
+    fn size_not_increased() {
+        let minimizer = Minimizer::run_with("test".to_owned(), case(), 10_000, failure)
+            .expect("The case must fail");
+
+        let original_size = minimizer.original_size;
+        let size = minimizer.size();
+        assert!(original_size.accounts >= size.accounts);
+        assert!(original_size.storage_slots >= size.storage_slots);
+        assert!(original_size.calldata_length >= size.calldata_length);
+        assert!(original_size.instructions >= size.instructions);
+        assert!(original_size.expectations >= size.expectations);
+        assert_ne!(original_size, size);
+    }
+
+    #[test]
+    fn max_runs_respected() {
+        for max_runs in [1, 2, 5, 20] {
+            let calls = Cell::new(0);
+            let minimizer = Minimizer::run_with("test".to_owned(), case(), max_runs, |case| {
+                calls.set(calls.get() + 1);
+                failure(case)
+            })
+            .expect("The case must fail");
+
+            assert!(calls.get() <= max_runs);
+            assert_eq!(minimizer.runs, calls.get());
+            assert_eq!(failure(&minimizer.case).as_deref(), Some("synthetic"));
+        }
+    }
+
+    #[test]
+    fn passing_case_rejected() {
+        let mut case = case();
+        case.transaction.data.0.clear();
+        assert!(Minimizer::run_with("test".to_owned(), case, 10_000, failure).is_err());
+    }
+}
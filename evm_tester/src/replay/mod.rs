@@ -0,0 +1,68 @@
+//!
+//! The single case replay.
+//!
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::test::case::transaction::Transaction;
+use crate::test::case::Case;
+use crate::test::test_structure::pre_state::PreState;
+use crate::vm::eravm::system_context::EVMContext;
+use crate::vm::evm_account::EvmAccount;
+use crate::vm::output::ExecutionOutput;
+use crate::vm::state_test_vm::StateTestVm;
+
+///
+/// The single case replay.
+///
+/// Dumps everything the case is executed with and everything it produces, so a failing case
+/// can be inspected without any filters.
+///
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replay {
+    /// The full case name.
+    pub name: String,
+    /// The prestate accounts.
+    pub prestate: PreState,
+    /// The transaction.
+    pub transaction: Transaction,
+    /// The block and transaction context.
+    pub context: EVMContext,
+    /// The execution output.
+    pub output: ExecutionOutput,
+    /// The number of gas used.
+    pub gas: web3::types::U256,
+    /// The number of EraVM ergs used.
+    pub ergs: u64,
+    /// The number of executed cycles.
+    pub cycles: usize,
+    /// The complete post-state.
+    pub post_state: BTreeMap<web3::types::Address, EvmAccount>,
+}
+
+impl Replay {
+    ///
+    /// Executes the case on the specified VM and collects the dump.
+    ///
+    pub fn run<V>(name: String, case: Case, mut vm: V) -> anyhow::Result<Self>
+    where
+        V: StateTestVm,
+    {
+        let result = case.execute(&mut vm)?;
+
+        Ok(Self {
+            name,
+            context: case.context(),
+            prestate: case.prestate,
+            transaction: case.transaction,
+            output: result.output,
+            gas: result.gas,
+            ergs: result.ergs,
+            cycles: result.cycles,
+            post_state: vm.get_state().into_iter().collect(),
+        })
+    }
+}
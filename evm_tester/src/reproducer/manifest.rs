@@ -0,0 +1,52 @@
+//!
+//! The reproducer manifest.
+//!
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+///
+/// The reproducer manifest.
+///
+/// Describes the environment the failure has been observed in, so it can be told apart from
+/// failures caused by different system contracts or tester versions.
+///
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    /// The original full case name.
+    pub case: String,
+    /// The hard fork the state test is written for.
+    pub fork: String,
+    /// The tester version.
+    pub tester_version: String,
+    /// The system contract bytecode hashes by contract name.
+    pub system_contracts: BTreeMap<String, web3::types::H256>,
+    /// The expected value description of the failure.
+    pub expected: Option<String>,
+    /// The actual value description of the failure.
+    pub actual: Option<String>,
+}
+
+impl Manifest {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        case: String,
+        fork: String,
+        system_contracts: BTreeMap<String, web3::types::H256>,
+        expected: Option<String>,
+        actual: Option<String>,
+    ) -> Self {
+        Self {
+            case,
+            fork,
+            tester_version: env!("CARGO_PKG_VERSION").to_owned(),
+            system_contracts,
+            expected,
+            actual,
+        }
+    }
+}
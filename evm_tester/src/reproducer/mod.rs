@@ -0,0 +1,246 @@
+//!
+//! The reproducible state test.
+//!
+
+pub mod manifest;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::test::case::Case;
+use crate::test::filler_structure::AccountFillerStruct;
+use crate::test::filler_structure::U256Parsed;
+use crate::test::test_structure::info_section::InfoSection;
+use crate::test::test_structure::post_state::PostState;
+use crate::test::test_structure::post_state::PostStateIndexes;
+use crate::test::test_structure::transaction_section::TransactionSection;
+use crate::test::test_structure::TestStructure;
+use crate::vm::evm_account::EvmAccount;
+
+use self::manifest::Manifest;
+
+///
+/// The reproducible state test.
+///
+/// Is saved as a single-case state test JSON together with a filler JSON with the expected
+/// post-state, so it is loaded by the same code as the Ethereum tests.
+///
+#[derive(Debug)]
+pub struct Reproducer {
+    /// The test name.
+    pub name: String,
+    /// The state test.
+    pub test: TestStructure,
+    /// The expected post-state accounts in the filler format.
+    pub expected_state: BTreeMap<web3::types::Address, serde_json::Value>,
+    /// The manifest describing the environment the reproducer has been produced in.
+    pub manifest: Option<Manifest>,
+}
+
+impl Reproducer {
+    /// The hard fork the state tests are written for.
+    pub const FORK: &'static str = "Cancun";
+
+    ///
+    /// Creates a reproducer of the case with the specified expected post-state.
+    ///
+    pub fn new(
+        name: String,
+        case: &Case,
+        expected_state: HashMap<web3::types::Address, EvmAccount>,
+    ) -> Self {
+        let expected_state = expected_state
+            .into_iter()
+            .map(|(address, account)| (address, Self::account_expectation(&account)))
+            .collect();
+        Self::with_expectations(name, case, expected_state)
+    }
+
+    ///
+    /// Creates a reproducer of the case keeping its original filler expectations.
+    ///
+    pub fn from_expectations(name: String, case: &Case) -> Self {
+        let expected_state = case
+            .expected_state
+            .iter()
+            .map(|(address, account)| (*address, Self::filler_expectation(account)))
+            .collect();
+        Self::with_expectations(name, case, expected_state)
+    }
+
+    ///
+    /// Sets the manifest, which is written next to the state test.
+    ///
+    pub fn with_manifest(mut self, manifest: Manifest) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    ///
+    /// Creates a reproducer of the case with the expected post-state in the filler format.
+    ///
+    fn with_expectations(
+        name: String,
+        case: &Case,
+        expected_state: BTreeMap<web3::types::Address, serde_json::Value>,
+    ) -> Self {
+        let info = InfoSection {
+            comment: format!("Reproducer of `{name}`"),
+            filling_rpc_server: String::new(),
+            filling_tool_version: format!("evm-tester {}", env!("CARGO_PKG_VERSION")),
+            lllcversion: String::new(),
+            source: String::new(),
+            source_hash: String::new(),
+            labels: Some(HashMap::from([(0, case.label.clone())])),
+        };
+
+        let case_post_state = case.post_state.as_ref();
+        let post_state = PostState {
+            indexes: PostStateIndexes {
+                data: 0,
+                gas: 0,
+                value: 0,
+            },
+            hash: case_post_state
+                .map(|post_state| post_state.hash)
+                .unwrap_or_default(),
+            logs: case_post_state
+                .map(|post_state| post_state.logs)
+                .unwrap_or_default(),
+            txbytes: case_post_state
+                .map(|post_state| post_state.txbytes.clone())
+                .unwrap_or_default(),
+            expect_exception: case_post_state
+                .and_then(|post_state| post_state.expect_exception.clone()),
+        };
+
+        let transaction = TransactionSection {
+            data: vec![case.transaction.data.clone()],
+            gas_limit: vec![case.transaction.gas_limit],
+            gas_price: case.transaction.gas_price,
+            max_fee_per_gas: case.transaction.max_fee_per_gas,
+            max_priority_fee_per_gas: case.transaction.max_priority_fee_per_gas,
+            nonce: case.transaction.nonce,
+            secret_key: case.transaction.secret_key,
+            to: case.transaction.to,
+            sender: case.transaction.sender,
+            value: vec![case.transaction.value],
+            access_lists: case
+                .transaction
+                .access_list
+                .as_ref()
+                .map(|access_list| vec![Some(access_list.clone())]),
+        };
+
+        let test = TestStructure {
+            _info: info,
+            env: case.env.clone(),
+            post: HashMap::from([(Self::FORK.to_owned(), vec![post_state])]),
+            pre: case.prestate.clone(),
+            transaction,
+        };
+
+        Self {
+            name,
+            test,
+            expected_state,
+            manifest: None,
+        }
+    }
+
+    ///
+    /// Writes the state test, its filler and the manifest if any to the specified directory.
+    ///
+    /// Returns the state test file path.
+    ///
+    pub fn write(&self, directory: &Path) -> anyhow::Result<PathBuf> {
+        std::fs::create_dir_all(directory)?;
+
+        let test = BTreeMap::from([(self.name.as_str(), &self.test)]);
+        let test_path = directory.join(format!("{}.json", self.name));
+        std::fs::write(&test_path, serde_json::to_string_pretty(&test)?)?;
+
+        let filler_path = directory.join(format!("{}Filler.json", self.name));
+        std::fs::write(&filler_path, serde_json::to_string_pretty(&self.filler())?)?;
+
+        if let Some(manifest) = self.manifest.as_ref() {
+            let manifest_path = directory.join(format!("{}.manifest.json", self.name));
+            std::fs::write(&manifest_path, serde_json::to_string_pretty(manifest)?)?;
+        }
+
+        Ok(test_path)
+    }
+
+    ///
+    /// Returns the filler with the expected post-state.
+    ///
+    fn filler(&self) -> serde_json::Value {
+        let result: serde_json::Map<String, serde_json::Value> = self
+            .expected_state
+            .iter()
+            .map(|(address, account)| (format!("{address:?}"), account.clone()))
+            .collect();
+
+        let filler = serde_json::json!({
+            "expect": [{ "result": result }],
+        });
+        serde_json::Value::Object(serde_json::Map::from_iter([(self.name.clone(), filler)]))
+    }
+
+    ///
+    /// Converts the complete account state into the filler expectation.
+    ///
+    fn account_expectation(account: &EvmAccount) -> serde_json::Value {
+        let storage: BTreeMap<String, String> = account
+            .storage
+            .iter()
+            .map(|(key, value)| (format!("{key:#x}"), format!("{value:#x}")))
+            .collect();
+        serde_json::json!({
+            "balance": format!("{:#x}", account.balance),
+            "nonce": format!("{:#x}", account.nonce),
+            "code": format!("0x{}", hex::encode(account.code.as_slice())),
+            "storage": storage,
+        })
+    }
+
+    ///
+    /// Converts the original filler expectation back into the filler format, keeping only the
+    /// checked fields.
+    ///
+    fn filler_expectation(account: &AccountFillerStruct) -> serde_json::Value {
+        let mut expectation = serde_json::Map::new();
+        if let Some(balance) = account.balance.as_ref() {
+            expectation.insert("balance".to_owned(), Self::parsed_u256(balance).into());
+        }
+        if let Some(nonce) = account.nonce.as_ref() {
+            expectation.insert("nonce".to_owned(), Self::parsed_u256(nonce).into());
+        }
+        if let Some(code) = account.code.as_ref() {
+            expectation.insert(
+                "code".to_owned(),
+                format!("0x{}", hex::encode(code.0 .0.as_slice())).into(),
+            );
+        }
+        if let Some(storage) = account.storage.as_ref() {
+            let storage: BTreeMap<String, String> = AccountFillerStruct::parse_storage(storage)
+                .iter()
+                .map(|(key, value)| (Self::parsed_u256(key), Self::parsed_u256(value)))
+                .collect();
+            expectation.insert("storage".to_owned(), serde_json::json!(storage));
+        }
+        serde_json::Value::Object(expectation)
+    }
+
+    ///
+    /// Converts the filler value back into the filler format.
+    ///
+    fn parsed_u256(value: &U256Parsed) -> String {
+        match value {
+            U256Parsed::Value(value) => format!("{value:#x}"),
+            U256Parsed::Any => "ANY".to_owned(),
+        }
+    }
+}
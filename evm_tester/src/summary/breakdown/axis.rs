@@ -0,0 +1,45 @@
+//!
+//! The evm tester breakdown axis.
+//!
+
+use std::str::FromStr;
+
+///
+/// The property the tests are aggregated by in the breakdown.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// The test directory relative to the test suite root.
+    Directory,
+    /// The test group from the index.
+    Group,
+}
+
+impl FromStr for Axis {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "directory" => Ok(Self::Directory),
+            "group" => Ok(Self::Group),
+            string => anyhow::bail!(
+                "Unknown breakdown axis `{}`. Supported axes: {}",
+                string,
+                [Self::Directory, Self::Group]
+                    .into_iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Axis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Directory => write!(f, "directory"),
+            Self::Group => write!(f, "group"),
+        }
+    }
+}
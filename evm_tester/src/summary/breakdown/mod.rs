@@ -0,0 +1,145 @@
+//!
+//! The evm tester pass-rate breakdown.
+//!
+
+pub mod axis;
+pub mod order;
+pub mod row;
+
+use std::collections::BTreeMap;
+
+use colored::Colorize;
+
+use super::element::Element;
+
+use self::axis::Axis;
+use self::order::Order;
+use self::row::Row;
+
+///
+/// The outcome counters and the pass rate of the tests aggregated by directory or by group.
+///
+#[derive(Debug)]
+pub struct Breakdown {
+    /// The aggregation axis.
+    axis: Axis,
+    /// The directory or group names with their counters.
+    rows: Vec<(String, Row)>,
+}
+
+impl Breakdown {
+    /// The row name of the tests which do not belong to any group.
+    pub const NO_GROUP: &'static str = "<none>";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new<'a, I>(axis: Axis, elements: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Element>,
+    {
+        let mut rows: BTreeMap<String, Row> = BTreeMap::new();
+        for element in elements.into_iter() {
+            let name = match axis {
+                Axis::Directory => element.directory.as_str(),
+                Axis::Group => element.group.as_deref().unwrap_or(Self::NO_GROUP),
+            };
+            rows.entry(name.to_owned())
+                .or_default()
+                .push(&element.outcome);
+        }
+
+        Self {
+            axis,
+            rows: rows.into_iter().collect(),
+        }
+    }
+
+    ///
+    /// Sorts the rows in the specified order.
+    ///
+    pub fn sort(&mut self, order: Order) {
+        match order {
+            Order::Name => self.rows.sort_by(|a, b| a.0.cmp(&b.0)),
+            Order::Rate => self.rows.sort_by(|a, b| {
+                let a_rate = a.1.pass_rate().unwrap_or(f64::INFINITY);
+                let b_rate = b.1.pass_rate().unwrap_or(f64::INFINITY);
+                a_rate.total_cmp(&b_rate).then_with(|| a.0.cmp(&b.0))
+            }),
+            Order::Failures => self.rows.sort_by(|a, b| {
+                (b.1.failed + b.1.invalid)
+                    .cmp(&(a.1.failed + a.1.invalid))
+                    .then_with(|| a.0.cmp(&b.0))
+            }),
+            Order::Total => self
+                .rows
+                .sort_by(|a, b| b.1.total().cmp(&a.1.total()).then_with(|| a.0.cmp(&b.0))),
+        }
+    }
+
+    ///
+    /// Keeps only the rows whose counters differ from the same rows of the other breakdown.
+    ///
+    pub fn retain_changed(&mut self, other: &Self) {
+        let other: BTreeMap<&str, &Row> = other
+            .rows
+            .iter()
+            .map(|(name, row)| (name.as_str(), row))
+            .collect();
+        self.rows
+            .retain(|(name, row)| other.get(name.as_str()) != Some(&row));
+    }
+
+    ///
+    /// Keeps only the first rows up to the limit.
+    ///
+    pub fn truncate(&mut self, limit: usize) {
+        self.rows.truncate(limit);
+    }
+}
+
+impl std::fmt::Display for Breakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .rows
+            .iter()
+            .map(|(name, _)| name.len())
+            .chain(std::iter::once(self.axis.to_string().len()))
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "{}",
+            format!("Pass rate by {}:", self.axis).bright_white().bold()
+        )?;
+        writeln!(
+            f,
+            "  {:<width$} {:>8} {:>8} {:>8} {:>8} {:>8}",
+            self.axis.to_string().to_uppercase(),
+            "PASSED",
+            "FAILED",
+            "INVALID",
+            "IGNORED",
+            "RATE",
+        )?;
+        for (name, row) in self.rows.iter() {
+            let rate = match row.pass_rate() {
+                Some(rate) => format!("{rate:.2}%"),
+                None => "-".to_owned(),
+            };
+            let rate = if row.failed + row.invalid == 0 {
+                rate.green()
+            } else {
+                rate.bright_red()
+            };
+            writeln!(
+                f,
+                "  {:<width$} {:>8} {:>8} {:>8} {:>8} {:>8}",
+                name, row.passed, row.failed, row.invalid, row.ignored, rate,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+//!
+//! The evm tester breakdown order.
+//!
+
+use std::str::FromStr;
+
+///
+/// The order of the breakdown rows.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// By name, alphabetically.
+    Name,
+    /// By the pass rate, the lowest first.
+    Rate,
+    /// By the number of failed and invalid tests, the highest first.
+    Failures,
+    /// By the number of tests, the highest first.
+    Total,
+}
+
+impl FromStr for Order {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "name" => Ok(Self::Name),
+            "rate" => Ok(Self::Rate),
+            "failures" => Ok(Self::Failures),
+            "total" => Ok(Self::Total),
+            string => anyhow::bail!(
+                "Unknown breakdown order `{}`. Supported orders: {}",
+                string,
+                [Self::Name, Self::Rate, Self::Failures, Self::Total]
+                    .into_iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Order {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name => write!(f, "name"),
+            Self::Rate => write!(f, "rate"),
+            Self::Failures => write!(f, "failures"),
+            Self::Total => write!(f, "total"),
+        }
+    }
+}
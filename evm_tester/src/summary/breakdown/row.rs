@@ -0,0 +1,60 @@
+//!
+//! The evm tester breakdown row.
+//!
+
+use crate::summary::element::outcome::Outcome;
+
+///
+/// The outcome counters of the tests sharing a directory or a group.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Row {
+    /// The passed tests counter.
+    pub passed: usize,
+    /// The failed tests counter.
+    pub failed: usize,
+    /// The invalid tests counter.
+    pub invalid: usize,
+    /// The ignored tests counter.
+    pub ignored: usize,
+}
+
+impl Row {
+    ///
+    /// Counts the outcome in.
+    ///
+    pub fn push(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Passed { .. } => self.passed += 1,
+            Outcome::Failed { .. } => self.failed += 1,
+            Outcome::Invalid { .. } => self.invalid += 1,
+            Outcome::Ignored { .. } => self.ignored += 1,
+        }
+    }
+
+    ///
+    /// The number of executed tests.
+    ///
+    pub fn executed(&self) -> usize {
+        self.passed + self.failed + self.invalid
+    }
+
+    ///
+    /// The number of all tests, including the ignored ones.
+    ///
+    pub fn total(&self) -> usize {
+        self.executed() + self.ignored
+    }
+
+    ///
+    /// The percentage of the executed tests which have passed.
+    ///
+    /// Returns `None` if no tests have been executed.
+    ///
+    pub fn pass_rate(&self) -> Option<f64> {
+        match self.executed() {
+            0 => None,
+            executed => Some((self.passed as f64) * 100.0 / (executed as f64)),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//!
+//! The evm tester failure fingerprint.
+//!
+
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::vm::opcode;
+use crate::vm::output::ExecutionOutput;
+
+use super::outcome::category::Category;
+
+/// The hexadecimal number pattern, compiled once for all fingerprints.
+static HEXADECIMAL_NUMBER: OnceLock<regex::Regex> = OnceLock::new();
+
+/// The decimal number pattern, compiled once for all fingerprints.
+static DECIMAL_NUMBER: OnceLock<regex::Regex> = OnceLock::new();
+
+///
+/// The failure properties which stay the same across the cases failing because of the same bug.
+///
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fingerprint {
+    /// The failure category.
+    pub category: Category,
+    /// Whether the transaction has failed.
+    pub exception: bool,
+    /// The last executed EVM opcode mnemonic, if the case has been traced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opcode: Option<String>,
+    /// The normalized revert reason, if the transaction has reverted with any data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// The normalized error of the invalid case.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Fingerprint {
+    /// The `Error(string)` revert selector.
+    pub const SELECTOR_ERROR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+    /// The `Panic(uint256)` revert selector.
+    pub const SELECTOR_PANIC: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+    ///
+    /// A shortcut constructor for the failed cases.
+    ///
+    pub fn failed(
+        category: Category,
+        output: Option<&ExecutionOutput>,
+        opcode: Option<u8>,
+    ) -> Self {
+        Self {
+            category,
+            exception: output.is_some_and(|output| output.exception),
+            opcode: opcode.map(|opcode| opcode::name(opcode).to_owned()),
+            revert_reason: output.and_then(Self::revert_reason),
+            error: None,
+        }
+    }
+
+    ///
+    /// A shortcut constructor for the invalid cases.
+    ///
+    pub fn invalid(error: &str) -> Self {
+        Self {
+            category: Category::Invalid,
+            exception: false,
+            opcode: None,
+            revert_reason: None,
+            error: Some(Self::normalize(error)),
+        }
+    }
+
+    ///
+    /// Decodes the revert reason of the failed transaction, keeping only the custom error
+    /// selector and replacing the numbers in the error messages.
+    ///
+    fn revert_reason(output: &ExecutionOutput) -> Option<String> {
+        if !output.exception || output.return_data.is_empty() {
+            return None;
+        }
+
+        let mut data = Vec::with_capacity(output.return_data.len() * 32);
+        for word in output.return_data.iter() {
+            let mut bytes = [0u8; 32];
+            word.to_big_endian(&mut bytes);
+            data.extend_from_slice(&bytes);
+        }
+        if data.len() < 4 {
+            return Some(format!("0x{}", hex::encode(data.as_slice())));
+        }
+
+        let (selector, arguments) = data.split_at(4);
+        if selector == Self::SELECTOR_ERROR.as_slice() {
+            let message = arguments
+                .get(64..)
+                .and_then(|tail| {
+                    let length = web3::types::U256::from_big_endian(arguments.get(32..64)?);
+                    if length > web3::types::U256::from(tail.len()) {
+                        return None;
+                    }
+                    tail.get(..length.as_usize())
+                })
+                .map(String::from_utf8_lossy)
+                .unwrap_or_default();
+            Some(format!("Error({})", Self::normalize(message.as_ref())))
+        } else if selector == Self::SELECTOR_PANIC.as_slice() {
+            let code = arguments
+                .get(..32)
+                .map(web3::types::U256::from_big_endian)
+                .unwrap_or_default();
+            Some(format!("Panic({code:#x})"))
+        } else {
+            Some(format!("0x{}", hex::encode(selector)))
+        }
+    }
+
+    ///
+    /// Replaces the hexadecimal and decimal numbers, such as addresses and amounts.
+    ///
+    fn normalize(message: &str) -> String {
+        let message = HEXADECIMAL_NUMBER
+            .get_or_init(|| regex::Regex::new("0x[0-9A-Fa-f]+").expect("Always valid"))
+            .replace_all(message, "0x_");
+        DECIMAL_NUMBER
+            .get_or_init(|| regex::Regex::new("[0-9]+").expect("Always valid"))
+            .replace_all(message.as_ref(), "_")
+            .to_string()
+    }
+}
+
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.category)?;
+        if self.exception {
+            write!(f, ", exception")?;
+        }
+        if let Some(opcode) = self.opcode.as_ref() {
+            write!(f, ", last opcode {opcode}")?;
+        }
+        if let Some(revert_reason) = self.revert_reason.as_ref() {
+            write!(f, ", revert {revert_reason}")?;
+        }
+        if let Some(error) = self.error.as_ref() {
+            write!(f, ", error `{error}`")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+//!
+//! The evm tester summary element test fixtures.
+//!
+
+use crate::summary::element::outcome::category::Category;
+use crate::summary::element::outcome::ignore_reason::IgnoreReason;
+use crate::summary::element::outcome::passed_variant::PassedVariant;
+use crate::summary::element::outcome::Outcome;
+use crate::summary::element::Element;
+use crate::test::expected_failure::ExpectedFailure;
+
+///
+/// Returns the element with the specified outcome.
+///
+pub fn element(name: &str, outcome: Outcome) -> Element {
+    Element::new(name.to_owned(), "directory".to_owned(), None, outcome)
+}
+
+///
+/// Returns a passed element.
+///
+pub fn passed(name: &str) -> Element {
+    passed_with_cost(name, 1, 1)
+}
+
+///
+/// Returns a passed element with the specified ergs and gas.
+///
+pub fn passed_with_cost(name: &str, ergs: u64, gas: u64) -> Element {
+    let variant = PassedVariant::Runtime {
+        cycles: 1,
+        ergs,
+        gas: web3::types::U256::from(gas),
+    };
+    element(name, Outcome::passed(None, variant))
+}
+
+///
+/// Returns a failed element.
+///
+pub fn failed(name: &str) -> Element {
+    let outcome = Outcome::failed(
+        vec![0x01],
+        Category::Balance,
+        false,
+        Some("1".to_owned()),
+        Some("2".to_owned()),
+    );
+    element(name, outcome)
+}
+
+///
+/// Returns an invalid element.
+///
+pub fn invalid(name: &str) -> Element {
+    element(name, Outcome::invalid("Invalid test", vec![]))
+}
+
+///
+/// Returns an ignored element.
+///
+pub fn ignored(name: &str) -> Element {
+    element(name, Outcome::ignored(IgnoreReason::other("Not supported")))
+}
+
+///
+/// Marks the element as an expected failure.
+///
+pub fn expected_failure(mut element: Element) -> Element {
+    element.expected_failure = Some(ExpectedFailure {
+        cases: None,
+        reason: Some("Not implemented".to_owned()),
+        url: None,
+    });
+    element
+}
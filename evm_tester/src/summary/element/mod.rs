@@ -2,47 +2,95 @@
 //! The evm tester summary element.
 //!
 
+pub mod fingerprint;
+#[cfg(test)]
+pub mod fixtures;
 pub mod outcome;
 
 use colored::Colorize;
+use serde::Deserialize;
+use serde::Serialize;
 
+use crate::test::expected_failure::ExpectedFailure;
+
+use self::fingerprint::Fingerprint;
 use self::outcome::passed_variant::PassedVariant;
 use self::outcome::Outcome;
 
 ///
 /// The evm tester summary element.
 ///
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Element {
     /// The test name.
     pub name: String,
+    /// The test directory relative to the test suite root.
+    pub directory: String,
+    /// The test group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
     /// The test outcome.
     pub outcome: Outcome,
+    /// The wall-clock time of the case in seconds, including the VM cloning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<f64>,
+    /// The failure fingerprint, if the test has failed or is invalid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<Fingerprint>,
+    /// The expected failure of the test, if it is marked as such.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_failure: Option<ExpectedFailure>,
 }
 
 impl Element {
     ///
     /// A shortcut constructor.
     ///
-    pub fn new(name: String, outcome: Outcome) -> Self {
-        Self { name, outcome }
+    pub fn new(name: String, directory: String, group: Option<String>, outcome: Outcome) -> Self {
+        Self {
+            name,
+            directory,
+            group,
+            outcome,
+            time: None,
+            fingerprint: None,
+            expected_failure: None,
+        }
+    }
+
+    ///
+    /// Sets the failure fingerprint.
+    ///
+    pub fn with_fingerprint(mut self, fingerprint: Fingerprint) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
     }
 
     ///
     /// Prints the element.
     ///
     pub fn print(&self, verbosity: bool) -> Option<String> {
+        let is_expected_failure = self.expected_failure.is_some();
         match self.outcome {
-            Outcome::Passed { .. } if !verbosity => return None,
-            Outcome::Ignored => return None,
+            Outcome::Passed { .. } if !verbosity && !is_expected_failure => return None,
+            Outcome::Failed { .. } | Outcome::Invalid { .. }
+                if !verbosity && is_expected_failure =>
+            {
+                return None
+            }
+            Outcome::Ignored { .. } => return None,
             _ => {}
         }
 
         let outcome = match self.outcome {
+            Outcome::Passed { .. } if is_expected_failure => "XPASS".bright_yellow(),
+            Outcome::Failed { .. } | Outcome::Invalid { .. } if is_expected_failure => {
+                "XFAIL".bright_black()
+            }
             Outcome::Passed { .. } => "PASSED".green(),
             Outcome::Failed { .. } => "FAILED".bright_red(),
             Outcome::Invalid { .. } => "INVALID".red(),
-            Outcome::Ignored => "IGNORED".bright_black(),
+            Outcome::Ignored { .. } => "IGNORED".bright_black(),
         };
 
         let details = match self.outcome {
@@ -80,6 +128,7 @@ impl Element {
                 ref exception,
                 ref expected,
                 ref actual,
+                ..
             } => {
                 let actual_line = if let Some(actual_value) = actual {
                     format!("\n actual: {actual_value}")
@@ -103,6 +152,14 @@ impl Element {
             _ => String::new(),
         };
 
+        let details = match self.expected_failure {
+            Some(ref expected_failure) => format!(
+                "{details} {}",
+                format!("(expected failure: {})", expected_failure.description()).bright_white()
+            ),
+            None => details,
+        };
+
         Some(format!("{:>7} {} {}", outcome, self.name, details))
     }
 }
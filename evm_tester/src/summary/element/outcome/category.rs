@@ -0,0 +1,138 @@
+//!
+//! The evm tester failure category.
+//!
+
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The kind of value a failure is about, which often points to the subsystem at fault.
+///
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Category {
+    /// An account balance.
+    Balance,
+    /// An account nonce.
+    Nonce,
+    /// An account code.
+    Code,
+    /// A storage slot.
+    Storage,
+    /// The success flag of the transaction.
+    Exception,
+    /// The return data.
+    ReturnData,
+    /// The gas used.
+    Gas,
+    /// The emitted logs.
+    Logs,
+    /// The post-state root.
+    StateRoot,
+    /// The test could not be executed.
+    Invalid,
+    /// The tester or the VM has panicked.
+    Panic,
+    /// Anything else.
+    #[default]
+    Other,
+}
+
+impl Category {
+    /// All categories in the display order.
+    pub const ALL: [Self; 12] = [
+        Self::Balance,
+        Self::Nonce,
+        Self::Code,
+        Self::Storage,
+        Self::Exception,
+        Self::ReturnData,
+        Self::Gas,
+        Self::Logs,
+        Self::StateRoot,
+        Self::Invalid,
+        Self::Panic,
+        Self::Other,
+    ];
+}
+
+impl FromStr for Category {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match Self::ALL
+            .into_iter()
+            .find(|category| category.to_string() == string)
+        {
+            Some(category) => Ok(category),
+            None => anyhow::bail!(
+                "Unknown failure category `{}`. Supported categories: {}",
+                string,
+                Self::ALL
+                    .into_iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Balance => write!(f, "balance"),
+            Self::Nonce => write!(f, "nonce"),
+            Self::Code => write!(f, "code"),
+            Self::Storage => write!(f, "storage"),
+            Self::Exception => write!(f, "exception"),
+            Self::ReturnData => write!(f, "return-data"),
+            Self::Gas => write!(f, "gas"),
+            Self::Logs => write!(f, "logs"),
+            Self::StateRoot => write!(f, "state-root"),
+            Self::Invalid => write!(f, "invalid"),
+            Self::Panic => write!(f, "panic"),
+            Self::Other => write!(f, "other"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Category;
+
+    #[test]
+    fn display_from_str_round_trip() {
+        for category in Category::ALL.into_iter() {
+            let string = category.to_string();
+            assert_eq!(
+                Category::from_str(string.as_str()).expect("Always valid"),
+                category
+            );
+        }
+    }
+
+    #[test]
+    fn display_matches_serde() {
+        for category in Category::ALL.into_iter() {
+            assert_eq!(
+                serde_json::to_value(category).expect("Always valid"),
+                serde_json::Value::String(category.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn from_str_unknown() {
+        for string in ["", "Balance", "return_data", "returndata", "unknown"] {
+            let error = Category::from_str(string).expect_err("Must be rejected");
+            assert!(error.to_string().contains("return-data"));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//!
+//! The evm tester summary element ignore reason.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The reason a test is ignored.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IgnoreReason {
+    /// The emulator does not support the opcode.
+    UnsupportedOpcode {
+        /// The opcode byte.
+        opcode: u8,
+        /// The opcode mnemonic.
+        mnemonic: String,
+    },
+    /// The emulator does not support the precompile.
+    UnsupportedPrecompile {
+        /// The precompile address.
+        address: usize,
+        /// The precompile name.
+        name: String,
+    },
+    /// The test is skipped by the tester itself.
+    Other {
+        /// The reason description.
+        description: String,
+    },
+}
+
+impl IgnoreReason {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn unsupported_opcode(opcode: u8) -> Self {
+        Self::UnsupportedOpcode {
+            opcode,
+            mnemonic: crate::vm::opcode::name(opcode).to_owned(),
+        }
+    }
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn unsupported_precompile(address: usize) -> Self {
+        Self::UnsupportedPrecompile {
+            address,
+            name: crate::vm::precompile::name(address).to_owned(),
+        }
+    }
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn other(description: &str) -> Self {
+        Self::Other {
+            description: description.to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Display for IgnoreReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedOpcode { opcode, mnemonic } => {
+                write!(f, "Unsupported opcode {mnemonic} ({opcode:#04x})")
+            }
+            Self::UnsupportedPrecompile { address, name } => {
+                write!(f, "Unsupported precompile {name} ({address:#04x})")
+            }
+            Self::Other { description } => write!(f, "{description}"),
+        }
+    }
+}
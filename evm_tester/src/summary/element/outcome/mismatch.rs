@@ -0,0 +1,99 @@
+//!
+//! The evm tester summary element expectation mismatch.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::category::Category;
+
+///
+/// The first post-state value which does not match the test expectations.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mismatch {
+    /// The mismatching field: `balance`, `nonce`, `code`, `storage`, `stateRoot` or `exception`.
+    pub field: String,
+    /// The account address, which is zero for the state root and exception flag.
+    pub address: web3::types::Address,
+    /// The storage key, if the field is `storage`.
+    pub key: Option<web3::types::U256>,
+    /// The expected value.
+    pub expected: String,
+    /// The actual value, if it is reported.
+    pub actual: Option<String>,
+}
+
+impl Mismatch {
+    /// The balance field name.
+    pub const FIELD_BALANCE: &'static str = "balance";
+
+    /// The nonce field name.
+    pub const FIELD_NONCE: &'static str = "nonce";
+
+    /// The code field name.
+    pub const FIELD_CODE: &'static str = "code";
+
+    /// The storage field name.
+    pub const FIELD_STORAGE: &'static str = "storage";
+
+    /// The state root field name.
+    pub const FIELD_STATE_ROOT: &'static str = "stateRoot";
+
+    /// The exception flag field name.
+    pub const FIELD_EXCEPTION: &'static str = "exception";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        field: &str,
+        address: web3::types::Address,
+        key: Option<web3::types::U256>,
+        expected: String,
+        actual: Option<String>,
+    ) -> Self {
+        Self {
+            field: field.to_owned(),
+            address,
+            key,
+            expected,
+            actual,
+        }
+    }
+
+    ///
+    /// Returns the failure category of the mismatching field.
+    ///
+    pub fn category(&self) -> Category {
+        match self.field.as_str() {
+            Self::FIELD_BALANCE => Category::Balance,
+            Self::FIELD_NONCE => Category::Nonce,
+            Self::FIELD_CODE => Category::Code,
+            Self::FIELD_STORAGE => Category::Storage,
+            Self::FIELD_STATE_ROOT => Category::StateRoot,
+            Self::FIELD_EXCEPTION => Category::Exception,
+            _ => Category::Other,
+        }
+    }
+
+    ///
+    /// Describes the expected value along with the field it belongs to.
+    ///
+    pub fn expected_description(&self) -> String {
+        match (self.field.as_str(), self.key) {
+            (Self::FIELD_CODE, _) => format!("Code of {:?} is invalid", self.address),
+            (Self::FIELD_STATE_ROOT, _) => format!("State root: {}", self.expected),
+            (Self::FIELD_EXCEPTION, _) => format!("Exception: {}", self.expected),
+            (Self::FIELD_STORAGE, Some(key)) => {
+                format!("Storage of {:?}, {key:?}: {}", self.address, self.expected)
+            }
+            (field, _) => {
+                let mut field = field.to_owned();
+                field[..1].make_ascii_uppercase();
+                format!("{field} of {:?}: {}", self.address, self.expected)
+            }
+        }
+    }
+}
@@ -2,14 +2,24 @@
 //! The evm tester summary element outcome.
 //!
 
+pub mod category;
+pub mod ignore_reason;
+pub mod mismatch;
 pub mod passed_variant;
 
+use serde::Deserialize;
+use serde::Serialize;
+
+use self::category::Category;
+use self::ignore_reason::IgnoreReason;
+use self::mismatch::Mismatch;
 use self::passed_variant::PassedVariant;
 
 ///
 /// The evm tester summary element outcome.
 ///
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum Outcome {
     /// The `passed` outcome.
     Passed {
@@ -22,9 +32,14 @@ pub enum Outcome {
     Failed {
         /// The calldata.
         calldata: String,
+        /// The failure category.
+        #[serde(default)]
+        category: Category,
         exception: bool,
         expected: Option<String>,
         actual: Option<String>,
+        /// The structured expectation mismatch, if the failure is one.
+        mismatch: Option<Mismatch>,
     },
     /// The `invalid` outcome. The test is incorrect.
     Invalid {
@@ -33,7 +48,10 @@ pub enum Outcome {
         calldata: String,
     },
     /// The `ignored` outcome. The test is ignored.
-    Ignored,
+    Ignored {
+        /// The reason the test is ignored.
+        reason: IgnoreReason,
+    },
 }
 
 impl Outcome {
@@ -49,15 +67,32 @@ impl Outcome {
     ///
     pub fn failed(
         calldata: Vec<u8>,
+        category: Category,
         exception: bool,
         expected: Option<String>,
         actual: Option<String>,
     ) -> Self {
         Self::Failed {
             calldata: hex::encode(calldata.as_slice()),
+            category,
             exception,
             expected,
             actual,
+            mismatch: None,
+        }
+    }
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn mismatched(calldata: Vec<u8>, exception: bool, mismatch: Mismatch) -> Self {
+        Self::Failed {
+            calldata: hex::encode(calldata.as_slice()),
+            category: mismatch.category(),
+            exception,
+            expected: Some(mismatch.expected_description()),
+            actual: mismatch.actual.clone(),
+            mismatch: Some(mismatch),
         }
     }
 
@@ -77,7 +112,18 @@ impl Outcome {
     ///
     /// A shortcut constructor.
     ///
-    pub fn ignored() -> Self {
-        Self::Ignored
+    pub fn ignored(reason: IgnoreReason) -> Self {
+        Self::Ignored { reason }
+    }
+
+    ///
+    /// Returns the failure category, if the outcome is a failure.
+    ///
+    pub fn category(&self) -> Option<Category> {
+        match self {
+            Self::Failed { category, .. } => Some(*category),
+            Self::Invalid { .. } => Some(Category::Invalid),
+            _ => None,
+        }
     }
 }
@@ -2,10 +2,14 @@
 //! The evm tester summary element passed outcome variant.
 //!
 
+use serde::Deserialize;
+use serde::Serialize;
+
 ///
 /// The evm tester summary element passed outcome variant.
 ///
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum PassedVariant {
     /// The contract deploy.
     Deploy {
@@ -16,6 +20,7 @@ pub enum PassedVariant {
         /// The number of used ergs.
         ergs: u64,
         /// The number of used gas.
+        #[serde(rename = "gas")]
         _gas: u64,
     },
     /// The contract call.
@@ -30,3 +35,18 @@ pub enum PassedVariant {
     /// The special function call.
     Special,
 }
+
+impl PassedVariant {
+    ///
+    /// Returns the number of execution cycles, used ergs and used gas, if they are measured.
+    ///
+    pub fn costs(&self) -> Option<(usize, u64, u64)> {
+        match self {
+            Self::Deploy {
+                cycles, ergs, _gas, ..
+            } => Some((*cycles, *ergs, *_gas)),
+            Self::Runtime { cycles, ergs, gas } => Some((*cycles, *ergs, gas.low_u64())),
+            Self::Special => None,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//!
+//! The evm tester failures grouped by fingerprint.
+//!
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::utils;
+
+use super::element::fingerprint::Fingerprint;
+use super::element::Element;
+
+///
+/// The failed and invalid tests grouped by their fingerprints, the largest group first.
+///
+/// One bug usually fails many cases with the same fingerprint, so every group is shown with a
+/// single representative case.
+///
+#[derive(Debug)]
+pub struct FailureGroups<'a> {
+    /// The fingerprints with the names of their cases, sorted by name.
+    groups: Vec<(&'a Fingerprint, Vec<&'a str>)>,
+    /// The directory the failing cases have been exported to.
+    export_directory: Option<&'a Path>,
+}
+
+impl<'a> FailureGroups<'a> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(elements: &'a [Element], export_directory: Option<&'a Path>) -> Self {
+        let mut groups: BTreeMap<&'a Fingerprint, Vec<&'a str>> = BTreeMap::new();
+        for element in elements.iter() {
+            if let Some(fingerprint) = element.fingerprint.as_ref() {
+                groups
+                    .entry(fingerprint)
+                    .or_default()
+                    .push(element.name.as_str());
+            }
+        }
+
+        let mut groups: Vec<(&'a Fingerprint, Vec<&'a str>)> = groups.into_iter().collect();
+        for (_, names) in groups.iter_mut() {
+            names.sort();
+        }
+        groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+        Self {
+            groups,
+            export_directory,
+        }
+    }
+
+    ///
+    /// Returns the path of the exported reproducer of the case, if it exists.
+    ///
+    fn reproducer_path(&self, name: &str) -> Option<PathBuf> {
+        let path = self
+            .export_directory?
+            .join(format!("{}.json", utils::file_name(name)));
+        path.exists().then_some(path)
+    }
+}
+
+impl std::fmt::Display for FailureGroups<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} {} distinct of {} failures",
+            "Failures by fingerprint:".bright_white().bold(),
+            self.groups.len(),
+            self.groups
+                .iter()
+                .map(|(_, names)| names.len())
+                .sum::<usize>(),
+        )?;
+        for (fingerprint, names) in self.groups.iter() {
+            let representative = names.first().expect("Always exists");
+            writeln!(
+                f,
+                "{:>8} {}",
+                names.len().to_string().bright_red(),
+                fingerprint
+            )?;
+            writeln!(f, "         e.g. {representative}")?;
+            if let Some(path) = self.reproducer_path(representative) {
+                writeln!(f, "         reproducer {}", path.to_string_lossy())?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//!
+//! The evm tester ignore reason histogram.
+//!
+
+use std::collections::HashMap;
+
+use colored::Colorize;
+
+use super::element::outcome::ignore_reason::IgnoreReason;
+use super::element::outcome::Outcome;
+use super::element::Element;
+
+///
+/// The number of ignored tests per reason, most frequent first.
+///
+/// Shows how much of the suite is blocked on each unsupported opcode or precompile.
+///
+#[derive(Debug)]
+pub struct IgnoreHistogram<'a> {
+    /// The reasons with the number of tests they are ignoring.
+    reasons: Vec<(&'a IgnoreReason, usize)>,
+}
+
+impl<'a> IgnoreHistogram<'a> {
+    /// The width of the longest bar.
+    pub const BAR_WIDTH: usize = 40;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(elements: &'a [Element]) -> Self {
+        let mut counts: HashMap<&'a IgnoreReason, usize> = HashMap::new();
+        for element in elements.iter() {
+            if let Outcome::Ignored { ref reason } = element.outcome {
+                *counts.entry(reason).or_default() += 1;
+            }
+        }
+
+        let mut reasons: Vec<(&'a IgnoreReason, usize)> = counts.into_iter().collect();
+        reasons.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| a.0.to_string().cmp(&b.0.to_string()))
+        });
+        Self { reasons }
+    }
+}
+
+impl std::fmt::Display for IgnoreHistogram<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let maximum = match self.reasons.first() {
+            Some((_, count)) => *count,
+            None => return Ok(()),
+        };
+        let width = self
+            .reasons
+            .iter()
+            .map(|(reason, _)| reason.to_string().len())
+            .max()
+            .unwrap_or_default();
+
+        writeln!(f, "{}", "Ignored tests by reason:".bright_white().bold())?;
+        for (reason, count) in self.reasons.iter() {
+            let bar = "█".repeat((count * Self::BAR_WIDTH).div_ceil(maximum));
+            writeln!(
+                f,
+                "  {:<width$} {:>8} {}",
+                reason.to_string(),
+                count,
+                bar.bright_black(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,282 @@
+//!
+//! The evm tester JUnit XML report.
+//!
+
+use std::collections::BTreeMap;
+
+use super::element::outcome::Outcome;
+use super::element::Element;
+
+///
+/// The evm tester JUnit XML report.
+///
+/// Every test directory is a test suite, and every case is a test case. The failed cases are
+/// reported as failures, the invalid ones as errors, and the ignored ones as skipped.
+/// The expected failures are reported as skipped, and their unexpected passes as failures.
+///
+#[derive(Debug)]
+pub struct JUnit<'a> {
+    /// The elements grouped by the test directory and sorted by name.
+    suites: BTreeMap<&'a str, Vec<&'a Element>>,
+}
+
+impl<'a> JUnit<'a> {
+    /// The name of the root test suites element.
+    pub const NAME: &'static str = "evm-tester";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(elements: &'a [Element]) -> Self {
+        let mut suites: BTreeMap<&'a str, Vec<&'a Element>> = BTreeMap::new();
+        for element in elements.iter() {
+            suites
+                .entry(element.directory.as_str())
+                .or_default()
+                .push(element);
+        }
+        for elements in suites.values_mut() {
+            elements.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        Self { suites }
+    }
+
+    ///
+    /// Returns the number of tests, failures, errors and skipped tests of the elements.
+    ///
+    fn counters<'b, I>(elements: I) -> (usize, usize, usize, usize)
+    where
+        I: Iterator<Item = &'b Element>,
+    {
+        let mut counters = (0, 0, 0, 0);
+        for element in elements {
+            counters.0 += 1;
+            let is_expected_failure = element.expected_failure.is_some();
+            match element.outcome {
+                Outcome::Passed { .. } if is_expected_failure => counters.1 += 1,
+                Outcome::Failed { .. } | Outcome::Invalid { .. } if is_expected_failure => {
+                    counters.3 += 1
+                }
+                Outcome::Passed { .. } => {}
+                Outcome::Failed { .. } => counters.1 += 1,
+                Outcome::Invalid { .. } => counters.2 += 1,
+                Outcome::Ignored { .. } => counters.3 += 1,
+            }
+        }
+        counters
+    }
+
+    ///
+    /// Writes a single test case.
+    ///
+    fn fmt_element(
+        f: &mut std::fmt::Formatter<'_>,
+        directory: &str,
+        element: &Element,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "    <testcase name=\"{}\" classname=\"{}\"",
+            Self::escape(element.name.as_str()),
+            Self::escape(directory),
+        )?;
+        if let Some(time) = element.time {
+            write!(f, " time=\"{time:.3}\"")?;
+        }
+        if let Some(ref expected_failure) = element.expected_failure {
+            let description = Self::escape(expected_failure.description().as_str());
+            writeln!(f, ">")?;
+            match element.outcome {
+                Outcome::Passed { .. } => writeln!(
+                    f,
+                    "      <failure message=\"Unexpected pass: {description}\"/>"
+                )?,
+                Outcome::Failed { .. } | Outcome::Invalid { .. } => writeln!(
+                    f,
+                    "      <skipped message=\"Expected failure: {description}\"/>"
+                )?,
+                Outcome::Ignored { ref reason } => writeln!(
+                    f,
+                    "      <skipped message=\"{}\"/>",
+                    Self::escape(reason.to_string().as_str())
+                )?,
+            }
+            return writeln!(f, "    </testcase>");
+        }
+        match element.outcome {
+            Outcome::Passed { .. } => writeln!(f, "/>"),
+            Outcome::Failed {
+                ref calldata,
+                category,
+                exception,
+                ref expected,
+                ref actual,
+                ..
+            } => {
+                writeln!(f, ">")?;
+                writeln!(
+                    f,
+                    "      <failure message=\"{category} mismatch\">Exception: {exception}\nExpected: {}\nActual: {}\nCalldata: {}</failure>",
+                    Self::escape(expected.as_deref().unwrap_or("Unknown")),
+                    Self::escape(actual.as_deref().unwrap_or("Unknown")),
+                    calldata,
+                )?;
+                writeln!(f, "    </testcase>")
+            }
+            Outcome::Invalid {
+                ref error,
+                ref calldata,
+            } => {
+                writeln!(f, ">")?;
+                writeln!(
+                    f,
+                    "      <error message=\"{}\">Calldata: {calldata}</error>",
+                    Self::escape(error.as_str()),
+                )?;
+                writeln!(f, "    </testcase>")
+            }
+            Outcome::Ignored { ref reason } => {
+                writeln!(f, ">")?;
+                writeln!(
+                    f,
+                    "      <skipped message=\"{}\"/>",
+                    Self::escape(reason.to_string().as_str())
+                )?;
+                writeln!(f, "    </testcase>")
+            }
+        }
+    }
+
+    ///
+    /// Escapes the XML special characters, dropping the characters which are not allowed in XML.
+    ///
+    fn escape(value: &str) -> String {
+        let mut escaped = String::with_capacity(value.len());
+        for character in value.chars() {
+            match character {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                '\n' | '\r' | '\t' => escaped.push(character),
+                character if character.is_control() => {}
+                character => escaped.push(character),
+            }
+        }
+        escaped
+    }
+}
+
+impl std::fmt::Display for JUnit<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (tests, failures, errors, skipped) =
+            Self::counters(self.suites.values().flatten().copied());
+
+        writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            f,
+            "<testsuites name=\"{}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\">",
+            Self::NAME,
+        )?;
+        for (directory, elements) in self.suites.iter() {
+            let (tests, failures, errors, skipped) = Self::counters(elements.iter().copied());
+            let time: f64 = elements.iter().filter_map(|element| element.time).sum();
+            writeln!(
+                f,
+                "  <testsuite name=\"{}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\" time=\"{time:.3}\">",
+                Self::escape(directory),
+            )?;
+            for element in elements.iter() {
+                Self::fmt_element(f, directory, element)?;
+            }
+            writeln!(f, "  </testsuite>")?;
+        }
+        writeln!(f, "</testsuites>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::summary::element::fixtures;
+
+    use super::JUnit;
+
+    #[test]
+    fn escape_special_characters() {
+        assert_eq!(
+            JUnit::escape(r#"a & b < c > d "e" 'f'"#),
+            "a &amp; b &lt; c &gt; d &quot;e&quot; &apos;f&apos;"
+        );
+        assert_eq!(JUnit::escape("&amp;"), "&amp;amp;");
+    }
+
+    #[test]
+    fn escape_control_characters() {
+        assert_eq!(JUnit::escape("a\u{0}b\u{8}c\u{1b}[0md\u{7f}"), "abc[0md");
+        assert_eq!(JUnit::escape("a\nb\tc\r"), "a\nb\tc\r");
+        assert_eq!(JUnit::escape("unicode ✓"), "unicode ✓");
+    }
+
+    #[test]
+    fn counters() {
+        let cases = [
+            (fixtures::passed("passed"), (1, 0, 0, 0)),
+            (fixtures::failed("failed"), (1, 1, 0, 0)),
+            (fixtures::invalid("invalid"), (1, 0, 1, 0)),
+            (fixtures::ignored("ignored"), (1, 0, 0, 1)),
+            (
+                fixtures::expected_failure(fixtures::failed("xfail")),
+                (1, 0, 0, 1),
+            ),
+            (
+                fixtures::expected_failure(fixtures::invalid("xfail_invalid")),
+                (1, 0, 0, 1),
+            ),
+            (
+                fixtures::expected_failure(fixtures::passed("xpass")),
+                (1, 1, 0, 0),
+            ),
+        ];
+        for (element, counters) in cases.iter() {
+            assert_eq!(
+                JUnit::counters(std::iter::once(element)),
+                *counters,
+                "{}",
+                element.name
+            );
+        }
+
+        assert_eq!(
+            JUnit::counters(cases.iter().map(|(element, _)| element)),
+            (7, 2, 1, 3)
+        );
+    }
+
+    #[test]
+    fn report() {
+        let elements = vec![
+            fixtures::expected_failure(fixtures::failed("xfail")),
+            fixtures::expected_failure(fixtures::passed("xpass")),
+            fixtures::invalid("invalid"),
+        ];
+        let report = JUnit::new(elements.as_slice()).to_string();
+
+        assert!(report.contains(
+            "<testsuites name=\"evm-tester\" tests=\"3\" failures=\"1\" errors=\"1\" skipped=\"1\">"
+        ));
+        assert!(report.contains("<skipped message=\"Expected failure: Not implemented\"/>"));
+        assert!(report.contains("<failure message=\"Unexpected pass: Not implemented\"/>"));
+        assert!(report.contains("<error message=\"Invalid test\">"));
+    }
+
+    #[test]
+    fn failure_message() {
+        let elements = vec![fixtures::failed("failed")];
+        let report = JUnit::new(elements.as_slice()).to_string();
+
+        assert!(report.contains(
+            "<failure message=\"balance mismatch\">Exception: false\nExpected: 1\nActual: 2\nCalldata: 01</failure>"
+        ));
+    }
+}
@@ -2,16 +2,42 @@
 //! The evm tester summary.
 //!
 
+pub mod breakdown;
 pub mod element;
+pub mod failure_groups;
+pub mod ignore_histogram;
+pub mod junit;
+pub mod progress;
+pub mod test_time;
+pub mod timings;
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 
 use colored::Colorize;
 
+use crate::test::expected_failure::ExpectedFailure;
+
+use self::breakdown::axis::Axis as BreakdownAxis;
+use self::breakdown::Breakdown;
+use self::element::fingerprint::Fingerprint;
+use self::element::outcome::category::Category;
+use self::element::outcome::ignore_reason::IgnoreReason;
+use self::element::outcome::mismatch::Mismatch;
 use self::element::outcome::passed_variant::PassedVariant;
 use self::element::outcome::Outcome;
 use self::element::Element;
+use self::failure_groups::FailureGroups;
+use self::ignore_histogram::IgnoreHistogram;
+use self::junit::JUnit;
+use self::progress::Progress;
+use self::test_time::TestTime;
+use self::timings::Timings;
 
 ///
 /// The evm tester summary.
@@ -32,12 +58,31 @@ pub struct Summary {
     invalid: usize,
     /// The ignored tests counter.
     ignored: usize,
+    /// The expectedly failed tests counter.
+    xfailed: usize,
+    /// The unexpectedly passed tests counter.
+    xpassed: usize,
+    /// The failed and invalid tests counters by category.
+    categories: BTreeMap<Category, usize>,
+    /// The categories of the failures to print, all of them if empty.
+    category_filter: Vec<Category>,
+    /// The progress display, if the total number of cases is known.
+    progress: Option<Progress>,
+    /// The test file times.
+    test_times: Vec<TestTime>,
+    /// The start times of the cases which have not been reported yet.
+    case_starts: HashMap<String, Instant>,
+    /// The expected failures of the cases which have not been reported yet.
+    expected_failures: HashMap<String, ExpectedFailure>,
 }
 
 impl Summary {
     /// The elements vector default capacity.
     pub const ELEMENTS_INITIAL_CAPACITY: usize = 1024 * 4096;
 
+    /// The number of the slowest test files written to the JSON results without `--slowest`.
+    pub const SLOWEST_DEFAULT_LIMIT: usize = 20;
+
     ///
     /// A shortcut constructor.
     ///
@@ -50,25 +95,129 @@ impl Summary {
             failed: 0,
             invalid: 0,
             ignored: 0,
+            xfailed: 0,
+            xpassed: 0,
+            categories: BTreeMap::new(),
+            category_filter: Vec::new(),
+            progress: None,
+            test_times: Vec::new(),
+            case_starts: HashMap::new(),
+            expected_failures: HashMap::new(),
+        }
+    }
+
+    ///
+    /// Sets the categories of the failures to print. The passed tests are not printed either.
+    ///
+    pub fn with_category_filter(mut self, categories: Vec<Category>) -> Self {
+        self.category_filter = categories;
+        self
+    }
+
+    ///
+    /// Starts displaying the progress of the specified number of cases, unless the output is
+    /// suppressed.
+    ///
+    pub fn start_progress(&mut self, total: usize) {
+        if !self.quiet {
+            self.progress = Some(Progress::new(total));
         }
     }
 
     ///
     /// Whether the test run has been successful.
     ///
+    ///
+    /// The expected failures do not fail the run, but their unexpected passes do.
+    ///
     pub fn is_successful(&self) -> bool {
         for element in self.elements.iter() {
             match element.outcome {
+                Outcome::Passed { .. } if element.expected_failure.is_some() => return false,
+                _ if element.expected_failure.is_some() => continue,
                 Outcome::Passed { .. } => continue,
                 Outcome::Failed { .. } => return false,
                 Outcome::Invalid { .. } => return false,
-                Outcome::Ignored => continue,
+                Outcome::Ignored { .. } => continue,
             }
         }
 
         true
     }
 
+    ///
+    /// Writes the counters, all elements and the `limit` slowest test files to the specified file
+    /// as JSON.
+    ///
+    pub fn write_json(&self, path: &Path, limit: usize) -> anyhow::Result<()> {
+        let results = serde_json::json!({
+            "passed": self.passed,
+            "failed": self.failed,
+            "invalid": self.invalid,
+            "ignored": self.ignored,
+            "xfailed": self.xfailed,
+            "xpassed": self.xpassed,
+            "categories": self.categories,
+            "tests": self.timings(limit).tests(),
+            "elements": self.elements,
+        });
+        std::fs::write(path, serde_json::to_string_pretty(&results)?).map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to write the results to `{}`: {error}",
+                path.to_string_lossy()
+            )
+        })
+    }
+
+    ///
+    /// Writes all elements to the specified file as a JUnit XML report.
+    ///
+    pub fn write_junit(&self, path: &Path) -> anyhow::Result<()> {
+        let report = JUnit::new(self.elements.as_slice()).to_string();
+        std::fs::write(path, report).map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to write the JUnit report to `{}`: {error}",
+                path.to_string_lossy()
+            )
+        })
+    }
+
+    ///
+    /// Returns the histogram of the ignore reasons.
+    ///
+    pub fn ignore_histogram(&self) -> IgnoreHistogram<'_> {
+        IgnoreHistogram::new(self.elements.as_slice())
+    }
+
+    ///
+    /// Returns the failures grouped by fingerprint, pointing to the reproducers in the export
+    /// directory, if it is set.
+    ///
+    pub fn failure_groups<'a>(&'a self, export_directory: Option<&'a Path>) -> FailureGroups<'a> {
+        FailureGroups::new(self.elements.as_slice(), export_directory)
+    }
+
+    ///
+    /// Returns the specified number of the slowest test files and cases.
+    ///
+    pub fn timings(&self, limit: usize) -> Timings<'_> {
+        Timings::new(self.elements.as_slice(), self.test_times.as_slice(), limit)
+    }
+
+    ///
+    /// Returns the pass-rate breakdown by the specified axis.
+    ///
+    pub fn breakdown(&self, axis: BreakdownAxis) -> Breakdown {
+        Breakdown::new(axis, self.elements.iter())
+    }
+
+    ///
+    /// Returns the collected elements.
+    ///
+    pub fn elements(&self) -> &[Element] {
+        self.elements.as_slice()
+    }
+
     ///
     /// Wraps data into a thread-safe shared reference.
     ///
@@ -79,11 +228,51 @@ impl Summary {
     ///
     /// Extracts the data from the thread-safe shared reference.
     ///
+    /// The progress bar is erased, so that the final output can be printed.
+    ///
     pub fn unwrap_arc(summary: Arc<Mutex<Self>>) -> Self {
-        Arc::try_unwrap(summary)
+        let mut summary = Arc::try_unwrap(summary)
             .expect("Last shared reference")
             .into_inner()
-            .expect("Last shared reference")
+            .expect("Last shared reference");
+        if let Some(progress) = summary.progress.as_mut() {
+            progress.clear();
+        }
+        summary
+    }
+
+    ///
+    /// Marks the case as expected to fail, before its outcome is added.
+    ///
+    pub fn expect_failure(
+        summary: Arc<Mutex<Self>>,
+        name: String,
+        expected_failure: ExpectedFailure,
+    ) {
+        summary
+            .lock()
+            .expect("Sync")
+            .expected_failures
+            .insert(name, expected_failure);
+    }
+
+    ///
+    /// Starts timing the case, which is stopped when its outcome is added.
+    ///
+    pub fn start_case(summary: Arc<Mutex<Self>>, name: String) {
+        summary
+            .lock()
+            .expect("Sync")
+            .case_starts
+            .insert(name, Instant::now());
+    }
+
+    ///
+    /// Adds the time of a test file.
+    ///
+    pub fn test_time(summary: Arc<Mutex<Self>>, name: String, directory: String, time: Duration) {
+        let test_time = TestTime::new(name, directory, time.as_secs_f64());
+        summary.lock().expect("Sync").test_times.push(test_time);
     }
 
     ///
@@ -92,6 +281,7 @@ impl Summary {
     pub fn passed_deploy(
         summary: Arc<Mutex<Self>>,
         name: String,
+        directory: String,
         group: Option<String>,
         size: usize,
         cycles: usize,
@@ -104,7 +294,7 @@ impl Summary {
             ergs,
             _gas: gas,
         };
-        Self::passed(summary, name, group, passed_variant);
+        Self::passed(summary, name, directory, group, passed_variant);
     }
 
     ///
@@ -113,21 +303,27 @@ impl Summary {
     pub fn passed_runtime(
         summary: Arc<Mutex<Self>>,
         name: String,
+        directory: String,
         group: Option<String>,
         cycles: usize,
         ergs: u64,
         gas: web3::types::U256,
     ) {
         let passed_variant = PassedVariant::Runtime { cycles, ergs, gas };
-        Self::passed(summary, name, group, passed_variant);
+        Self::passed(summary, name, directory, group, passed_variant);
     }
 
     ///
     /// Adds a passed outcome of a special call, like `storageEmpty` or `balance`.
     ///
-    pub fn passed_special(summary: Arc<Mutex<Self>>, name: String, group: Option<String>) {
+    pub fn passed_special(
+        summary: Arc<Mutex<Self>>,
+        name: String,
+        directory: String,
+        group: Option<String>,
+    ) {
         let passed_variant = PassedVariant::Special;
-        Self::passed(summary, name, group, passed_variant);
+        Self::passed(summary, name, directory, group, passed_variant);
     }
 
     ///
@@ -136,31 +332,79 @@ impl Summary {
     pub fn failed(
         summary: Arc<Mutex<Self>>,
         name: String,
+        directory: String,
+        group: Option<String>,
+        category: Category,
         exception: bool,
         expected: Option<String>,
         actual: Option<String>,
         calldata: Vec<u8>,
+        fingerprint: Fingerprint,
     ) {
-        let element = Element::new(name, Outcome::failed(calldata, exception, expected, actual));
+        let element = Element::new(
+            name,
+            directory,
+            group,
+            Outcome::failed(calldata, category, exception, expected, actual),
+        )
+        .with_fingerprint(fingerprint);
+        summary.lock().expect("Sync").push_element(element);
+    }
+
+    ///
+    /// Adds a failed outcome caused by an expectation mismatch.
+    ///
+    pub fn mismatched(
+        summary: Arc<Mutex<Self>>,
+        name: String,
+        directory: String,
+        group: Option<String>,
+        exception: bool,
+        mismatch: Mismatch,
+        calldata: Vec<u8>,
+        fingerprint: Fingerprint,
+    ) {
+        let element = Element::new(
+            name,
+            directory,
+            group,
+            Outcome::mismatched(calldata, exception, mismatch),
+        )
+        .with_fingerprint(fingerprint);
         summary.lock().expect("Sync").push_element(element);
     }
 
     ///
     /// Adds an invalid outcome.
     ///
-    pub fn invalid<S>(summary: Arc<Mutex<Self>>, name: String, error: S, calldata: Vec<u8>)
-    where
+    pub fn invalid<S>(
+        summary: Arc<Mutex<Self>>,
+        name: String,
+        directory: String,
+        group: Option<String>,
+        error: S,
+        calldata: Vec<u8>,
+    ) where
         S: ToString,
     {
-        let element = Element::new(name, Outcome::invalid(error, calldata));
+        let error = error.to_string();
+        let fingerprint = Fingerprint::invalid(error.as_str());
+        let element = Element::new(name, directory, group, Outcome::invalid(error, calldata))
+            .with_fingerprint(fingerprint);
         summary.lock().expect("Sync").push_element(element);
     }
 
     ///
     /// Adds an ignored outcome.
     ///
-    pub fn ignored(summary: Arc<Mutex<Self>>, name: String) {
-        let element = Element::new(name, Outcome::ignored());
+    pub fn ignored(
+        summary: Arc<Mutex<Self>>,
+        name: String,
+        directory: String,
+        group: Option<String>,
+        reason: IgnoreReason,
+    ) {
+        let element = Element::new(name, directory, group, Outcome::ignored(reason));
         summary.lock().expect("Sync").push_element(element);
     }
 
@@ -170,22 +414,62 @@ impl Summary {
     fn passed(
         summary: Arc<Mutex<Self>>,
         name: String,
+        directory: String,
         group: Option<String>,
         passed_variant: PassedVariant,
     ) {
-        let element = Element::new(name, Outcome::passed(group, passed_variant));
+        let element = Element::new(
+            name,
+            directory,
+            group.clone(),
+            Outcome::passed(group, passed_variant),
+        );
         summary.lock().expect("Sync").push_element(element);
     }
 
     ///
     /// Pushes an element to the summary, printing it.
     ///
-    fn push_element(&mut self, element: Element) {
-        if let Some(string) = element.print(self.verbosity) {
-            println!("{string}");
+    fn push_element(&mut self, mut element: Element) {
+        element.expected_failure = self.expected_failures.remove(element.name.as_str());
+        element.time = self
+            .case_starts
+            .remove(element.name.as_str())
+            .map(|start| start.elapsed().as_secs_f64());
+
+        if let Some(category) = element
+            .outcome
+            .category()
+            .filter(|_| element.expected_failure.is_none())
+        {
+            *self.categories.entry(category).or_default() += 1;
+        }
+
+        let is_filtered_out = !self.category_filter.is_empty()
+            && !element
+                .outcome
+                .category()
+                .is_some_and(|category| self.category_filter.contains(&category));
+        if !is_filtered_out {
+            if let Some(string) = element.print(self.verbosity) {
+                if let Some(progress) = self.progress.as_mut() {
+                    progress.clear();
+                }
+                println!("{string}");
+            }
         }
 
         let is_executed = match element.outcome {
+            Outcome::Passed { .. } if element.expected_failure.is_some() => {
+                self.xpassed += 1;
+                true
+            }
+            Outcome::Failed { .. } | Outcome::Invalid { .. }
+                if element.expected_failure.is_some() =>
+            {
+                self.xfailed += 1;
+                true
+            }
             Outcome::Passed { .. } => {
                 self.passed += 1;
                 true
@@ -198,24 +482,38 @@ impl Summary {
                 self.invalid += 1;
                 true
             }
-            Outcome::Ignored => {
+            Outcome::Ignored { .. } => {
                 self.ignored += 1;
                 false
             }
         };
 
-        if is_executed {
+        let is_progress_on_terminal = self
+            .progress
+            .as_ref()
+            .is_some_and(|progress| progress.is_terminal());
+        if is_executed && !is_progress_on_terminal {
             let milestone = if self.verbosity {
                 usize::pow(10, 3)
             } else {
                 usize::pow(10, 5)
             };
 
-            if (self.passed + self.failed + self.invalid) % milestone == 0 {
+            if (self.passed + self.failed + self.invalid + self.xfailed + self.xpassed) % milestone
+                == 0
+            {
                 println!("{self}");
             }
         }
 
+        let (passed, failed) = (
+            self.passed + self.xpassed,
+            self.failed + self.invalid + self.xfailed,
+        );
+        if let Some(progress) = self.progress.as_mut() {
+            progress.advance(element.directory.as_str(), passed, failed);
+        }
+
         self.elements.push(element);
     }
 }
@@ -258,10 +556,30 @@ impl std::fmt::Display for Summary {
             "IGNORED".bright_black(),
             self.ignored.to_string().bright_black(),
         )?;
+        for (category, count) in self.categories.iter() {
+            writeln!(
+                f,
+                "║       {:<20}                    {:10}     ║",
+                category.to_string().bright_red(),
+                count.to_string().bright_red(),
+            )?;
+        }
+        writeln!(
+            f,
+            "║     {:7}                                   {:10}     ║",
+            "XFAIL".bright_black(),
+            self.xfailed.to_string().bright_black(),
+        )?;
+        writeln!(
+            f,
+            "║     {:7}                                   {:10}     ║",
+            "XPASS".bright_yellow(),
+            self.xpassed.to_string().bright_yellow(),
+        )?;
         writeln!(
             f,
             "║               {:10} TESTS MILESTONE                     ║",
-            self.passed + self.failed + self.invalid,
+            self.passed + self.failed + self.invalid + self.xfailed + self.xpassed,
         )?;
         writeln!(
             f,
@@ -271,3 +589,54 @@ impl std::fmt::Display for Summary {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::summary::element::fixtures;
+    use crate::summary::element::outcome::category::Category;
+    use crate::summary::element::Element;
+
+    use super::Summary;
+
+    fn summary(elements: Vec<Element>) -> Summary {
+        let mut summary = Summary::new(false, true);
+        for mut element in elements.into_iter() {
+            if let Some(expected_failure) = element.expected_failure.take() {
+                summary
+                    .expected_failures
+                    .insert(element.name.clone(), expected_failure);
+            }
+            summary.push_element(element);
+        }
+        summary
+    }
+
+    #[test]
+    fn is_successful() {
+        assert!(summary(vec![
+            fixtures::passed("passed"),
+            fixtures::ignored("ignored")
+        ])
+        .is_successful());
+        assert!(!summary(vec![fixtures::failed("failed")]).is_successful());
+        assert!(!summary(vec![fixtures::invalid("invalid")]).is_successful());
+        assert!(summary(vec![
+            fixtures::expected_failure(fixtures::failed("xfail")),
+            fixtures::expected_failure(fixtures::invalid("xfail_invalid")),
+        ])
+        .is_successful());
+        assert!(
+            !summary(vec![fixtures::expected_failure(fixtures::passed("xpass"))]).is_successful()
+        );
+    }
+
+    #[test]
+    fn categories() {
+        let summary = summary(vec![
+            fixtures::failed("failed"),
+            fixtures::expected_failure(fixtures::failed("xfail")),
+        ]);
+
+        assert_eq!(summary.categories.get(&Category::Balance), Some(&1));
+    }
+}
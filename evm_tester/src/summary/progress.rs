@@ -0,0 +1,162 @@
+//!
+//! The evm tester progress display.
+//!
+
+use std::io::IsTerminal;
+use std::io::Write;
+use std::time::Duration;
+use std::time::Instant;
+
+use colored::Colorize;
+
+///
+/// The progress of the test run.
+///
+/// Is redrawn in place on a terminal, and printed as a plain line from time to time otherwise.
+///
+#[derive(Debug)]
+pub struct Progress {
+    /// The number of cases to run.
+    total: usize,
+    /// The number of reported cases.
+    done: usize,
+    /// The run start time.
+    start: Instant,
+    /// The time of the last update.
+    last_update: Option<Instant>,
+    /// Whether the standard output is a terminal.
+    is_terminal: bool,
+    /// Whether the progress bar is currently drawn on the terminal.
+    is_drawn: bool,
+}
+
+impl Progress {
+    /// The width of the progress bar.
+    pub const BAR_WIDTH: usize = 30;
+
+    /// The maximum length of the directory shown.
+    pub const DIRECTORY_WIDTH: usize = 40;
+
+    /// The interval between the redraws on a terminal.
+    pub const TERMINAL_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// The interval between the plain progress lines.
+    pub const PLAIN_INTERVAL: Duration = Duration::from_secs(10);
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            done: 0,
+            start: Instant::now(),
+            last_update: None,
+            is_terminal: std::io::stdout().is_terminal(),
+            is_drawn: false,
+        }
+    }
+
+    ///
+    /// Counts a reported case in, updating the display if the interval has passed.
+    ///
+    pub fn advance(&mut self, directory: &str, passed: usize, failed: usize) {
+        self.done += 1;
+
+        let now = Instant::now();
+        let interval = if self.is_terminal {
+            Self::TERMINAL_INTERVAL
+        } else {
+            Self::PLAIN_INTERVAL
+        };
+        if self.done < self.total
+            && self
+                .last_update
+                .is_some_and(|last_update| now.duration_since(last_update) < interval)
+        {
+            return;
+        }
+        self.last_update = Some(now);
+
+        let line = self.line(directory, passed, failed);
+        if self.is_terminal {
+            print!("\r\x1b[2K{line}");
+            let _ = std::io::stdout().flush();
+            self.is_drawn = true;
+        } else {
+            println!("{line}");
+        }
+    }
+
+    ///
+    /// Erases the progress bar, so that other output can be printed to the terminal.
+    ///
+    pub fn clear(&mut self) {
+        if self.is_drawn {
+            print!("\r\x1b[2K");
+            self.is_drawn = false;
+        }
+    }
+
+    ///
+    /// Whether the progress is redrawn in place on a terminal.
+    ///
+    pub fn is_terminal(&self) -> bool {
+        self.is_terminal
+    }
+
+    ///
+    /// Formats the progress line.
+    ///
+    fn line(&self, directory: &str, passed: usize, failed: usize) -> String {
+        let done = self.done.min(self.total);
+        let ratio = if self.total == 0 {
+            1.0
+        } else {
+            (done as f64) / (self.total as f64)
+        };
+        let elapsed = self.start.elapsed();
+        let eta = if done == 0 {
+            Duration::ZERO
+        } else {
+            elapsed.mul_f64(((self.total - done) as f64) / (done as f64))
+        };
+        let directory = match directory.char_indices().nth_back(Self::DIRECTORY_WIDTH - 1) {
+            Some((index, _)) if index > 0 => format!("…{}", &directory[index..]),
+            _ => directory.to_owned(),
+        };
+
+        if self.is_terminal {
+            let filled = ((ratio * Self::BAR_WIDTH as f64) as usize).min(Self::BAR_WIDTH);
+            format!(
+                "[{}{}] {done}/{} {} {} ETA {} {}",
+                "█".repeat(filled).bright_green(),
+                "░".repeat(Self::BAR_WIDTH - filled).bright_black(),
+                self.total,
+                format!("passed {passed}").green(),
+                format!("failed {failed}").bright_red(),
+                Self::duration(eta),
+                directory.bright_black(),
+            )
+        } else {
+            format!(
+                "Progress: {done}/{} ({:.1}%), passed {passed}, failed {failed}, elapsed {}, ETA {}, {directory}",
+                self.total,
+                ratio * 100.0,
+                Self::duration(elapsed),
+                Self::duration(eta),
+            )
+        }
+    }
+
+    ///
+    /// Formats the duration as minutes and seconds.
+    ///
+    fn duration(duration: Duration) -> String {
+        format!(
+            "{}m{:02}s",
+            duration.as_secs() / 60,
+            duration.as_secs() % 60
+        )
+    }
+}
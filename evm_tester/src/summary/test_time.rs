@@ -0,0 +1,32 @@
+//!
+//! The evm tester test file time.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The wall-clock time of a test file, including the parsing and the VM cloning for every case.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestTime {
+    /// The test name.
+    pub name: String,
+    /// The test directory relative to the test suite root.
+    pub directory: String,
+    /// The time in seconds.
+    pub time: f64,
+}
+
+impl TestTime {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, directory: String, time: f64) -> Self {
+        Self {
+            name,
+            directory,
+            time,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//!
+//! The evm tester slowest tests report.
+//!
+
+use colored::Colorize;
+
+use super::element::Element;
+use super::test_time::TestTime;
+
+///
+/// The slowest test files and cases.
+///
+#[derive(Debug)]
+pub struct Timings<'a> {
+    /// The slowest test files, the slowest first.
+    tests: Vec<&'a TestTime>,
+    /// The slowest cases with their times, the slowest first.
+    cases: Vec<(&'a str, f64)>,
+}
+
+impl<'a> Timings<'a> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(elements: &'a [Element], test_times: &'a [TestTime], limit: usize) -> Self {
+        let mut tests: Vec<&'a TestTime> = test_times.iter().collect();
+        tests.sort_by(|a, b| b.time.total_cmp(&a.time));
+        tests.truncate(limit);
+
+        let mut cases: Vec<(&'a str, f64)> = elements
+            .iter()
+            .filter_map(|element| Some((element.name.as_str(), element.time?)))
+            .collect();
+        cases.sort_by(|a, b| b.1.total_cmp(&a.1));
+        cases.truncate(limit);
+
+        Self { tests, cases }
+    }
+
+    ///
+    /// Returns the slowest test files, the slowest first.
+    ///
+    pub fn tests(&self) -> &[&'a TestTime] {
+        self.tests.as_slice()
+    }
+}
+
+impl std::fmt::Display for Timings<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", "The slowest tests:".bright_white().bold())?;
+        for test in self.tests.iter() {
+            writeln!(
+                f,
+                "{:>10} {}/{}",
+                format!("{:.3}s", test.time).bright_yellow(),
+                test.directory,
+                test.name,
+            )?;
+        }
+        writeln!(f, "{}", "The slowest cases:".bright_white().bold())?;
+        for (name, time) in self.cases.iter() {
+            writeln!(f, "{:>10} {name}", format!("{time:.3}s").bright_yellow())?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use std::{
     collections::HashMap,
+    panic::AssertUnwindSafe,
+    path::Path,
     sync::{Arc, Mutex},
 };
 
@@ -9,11 +11,27 @@ pub mod transaction;
 use post_state_for_case::PostStateForCase;
 use transaction::Transaction;
 
+use era_compiler_common::EVMVersion;
+
 use crate::{
+    differential::Differential,
+    gas_report::GasReport,
+    gas_sweep::GasSweep,
+    reproducer::{manifest::Manifest, Reproducer},
+    summary::element::fingerprint::Fingerprint,
+    summary::element::outcome::category::Category,
+    summary::element::outcome::ignore_reason::IgnoreReason,
+    summary::element::outcome::mismatch::Mismatch,
     test::filler_structure::{AccountFillerStruct, Labels},
+    test::run_options::RunOptions,
+    trace::Trace,
     utils,
-    vm::eravm::system_context::SystemContext,
-    EraVM, EraVMDeployer, Summary,
+    vm::eravm::address_iterator_evm::EVMAddressIterator,
+    vm::eravm::system_context::{EVMContext, SystemContext},
+    vm::execution_result::ExecutionResult,
+    vm::state_root::StateRoot,
+    vm::state_test_vm::StateTestVm,
+    Summary,
 };
 
 use super::{
@@ -21,7 +39,7 @@ use super::{
     test_structure::{env_section::EnvSection, pre_state::PreState, TestStructure},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Case {
     /// The case label.
     pub label: String,
@@ -160,14 +178,30 @@ impl Case {
                         max_priority_fee_per_gas: test_definition
                             .transaction
                             .max_priority_fee_per_gas,
+                        access_list: test_definition
+                            .transaction
+                            .access_lists
+                            .as_ref()
+                            .and_then(|access_lists| access_lists.get(data_index).cloned())
+                            .flatten(),
                     };
 
-                    /*let post_state_for_case = PostStateForCase {
-                        hash: expected_result.hash,
-                        logs: expected_result.logs,
-                        txbytes: expected_result.txbytes.clone(),
-                        expect_exception: expected_result.expect_exception.clone(),
-                    };*/
+                    let post_state = test_definition
+                        .post
+                        .get(Reproducer::FORK)
+                        .and_then(|post_states| {
+                            post_states.iter().find(|post_state| {
+                                post_state.indexes.data == data_index
+                                    && post_state.indexes.gas == gas_limit_index
+                                    && post_state.indexes.value == value_index
+                            })
+                        })
+                        .map(|post_state| PostStateForCase {
+                            hash: post_state.hash,
+                            logs: post_state.logs,
+                            txbytes: post_state.txbytes.clone(),
+                            expect_exception: post_state.expect_exception.clone(),
+                        });
 
                     let mut expected_state_index: isize = -1;
 
@@ -192,7 +226,7 @@ impl Case {
                         label: label.unwrap_or(case_idx.to_string()),
                         prestate,
                         transaction,
-                        post_state: None,
+                        post_state,
                         expected_state: expected_state.clone(),
                         env: test_definition.env.clone(),
                     });
@@ -206,107 +240,706 @@ impl Case {
     }
 
     ///
-    /// Runs the case on EVM interpreter.
+    /// Runs the case on the specified VM, checking the filler expectations.
     ///
-    pub fn run_evm_interpreter<D, const M: bool>(
+    pub fn run<V>(
         self,
         summary: Arc<Mutex<Summary>>,
-        mut vm: EraVM,
+        mut vm: V,
         test_name: String,
+        test_directory: String,
         test_group: Option<String>,
+        options: &RunOptions,
     ) where
-        D: EraVMDeployer,
+        V: StateTestVm,
     {
-        let name = self.label;
+        let name = self.label.clone();
+
+        let mut last_opcode = None;
+        let run_result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            if options.trace {
+                let full_name = format!("{test_name}: {name}");
+                self.execute_traced(&mut vm, full_name.clone())
+                    .and_then(|(result, trace)| {
+                        trace.write(Path::new(Trace::DIRECTORY), full_name.as_str())?;
+                        last_opcode = trace.steps.last().map(|step| step.op);
+                        Ok(result)
+                    })
+            } else {
+                self.execute(&mut vm)
+            }
+        }));
+        let run_result = match run_result {
+            Ok(run_result) => run_result,
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "Unknown panic".to_owned());
+                Summary::failed(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory,
+                    test_group,
+                    Category::Panic,
+                    false,
+                    None,
+                    Some(message),
+                    self.transaction.data.0,
+                    Fingerprint::failed(Category::Panic, None, None),
+                );
+                return;
+            }
+        };
 
-        // Populate prestate
-        for (address, state) in self.prestate {
-            vm.set_balance(address, state.balance);
+        // TODO merge with prestate!
+        let mismatch = self
+            .check_expected_exception()
+            .or_else(|| Self::check_expected_state(&self.expected_state, &vm))
+            .or_else(|| self.check_state_root(&vm, options));
 
-            vm.set_nonce(address, state.nonce);
+        if let Ok(res) = run_result {
+            //println!("GAS USED: {:?}", res.gas);
+            if let Some(system_error) = res.output.system_error {
+                Summary::ignored(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory.clone(),
+                    test_group.clone(),
+                    Self::ignore_reason(system_error),
+                );
+                return;
+            }
+            /*if res.output.exception {
+                Summary::failed(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    Some("Finish successfully".to_string()),
+                    Some("Failed with exception".to_string()),
+                    self.transaction.data.0
+                );
+            } else {*/
+            match mismatch {
+                None => Summary::passed_runtime(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory.clone(),
+                    test_group,
+                    res.cycles,
+                    res.ergs,
+                    res.gas,
+                ),
+                Some(mismatch) => {
+                    self.export(
+                        options,
+                        &vm,
+                        format!("{test_name}: {name}"),
+                        Some(mismatch.expected_description()),
+                        mismatch.actual.clone(),
+                    );
+                    let fingerprint =
+                        Fingerprint::failed(mismatch.category(), Some(&res.output), last_opcode);
+                    Summary::mismatched(
+                        summary,
+                        format!("{test_name}: {name}"),
+                        test_directory.clone(),
+                        test_group.clone(),
+                        res.output.exception,
+                        mismatch,
+                        self.transaction.data.0,
+                        fingerprint,
+                    )
+                }
+            }
+            //}
+        } else {
+            let error = run_result.err().unwrap();
+            self.export(
+                options,
+                &vm,
+                format!("{test_name}: {name}"),
+                None,
+                Some(error.to_string()),
+            );
+            Summary::invalid(
+                summary,
+                format!("{test_name}: {name}"),
+                test_directory.clone(),
+                test_group.clone(),
+                error,
+                self.transaction.data.0,
+            );
+        }
+    }
+
+    ///
+    /// Exports the failing case as a reproducer with its original expectations, if the export
+    /// directory is set.
+    ///
+    fn export<V>(
+        &self,
+        options: &RunOptions,
+        vm: &V,
+        full_name: String,
+        expected: Option<String>,
+        actual: Option<String>,
+    ) where
+        V: StateTestVm,
+    {
+        let directory = match options.export_directory.as_ref() {
+            Some(directory) => directory,
+            None => return,
+        };
 
-            vm.set_predeployed_evm_contract(address, state.code.0);
+        let manifest = Manifest::new(
+            full_name.clone(),
+            Reproducer::FORK.to_owned(),
+            vm.system_contract_hashes(),
+            expected,
+            actual,
+        );
+        let reproducer = Reproducer::from_expectations(utils::file_name(full_name.as_str()), self)
+            .with_manifest(manifest);
+        if let Err(error) = reproducer.write(directory.as_path()) {
+            eprintln!("Failed to export the reproducer of `{full_name}`: {error}");
+        }
+    }
 
-            vm.populate_storage(
-                state
-                    .storage
-                    .into_iter()
-                    .map(|(storage_key, storage_value)| {
-                        ((address, storage_key), utils::u256_to_h256(&storage_value))
-                    })
-                    .collect(),
+    ///
+    /// Runs the case on both the emulator and the reference EVM, comparing the complete
+    /// resulting states instead of the filler expectations.
+    ///
+    /// Returns whether a divergence has been found.
+    ///
+    pub fn run_differential<E, R>(
+        self,
+        summary: Arc<Mutex<Summary>>,
+        mut emulator: E,
+        mut reference: R,
+        test_name: String,
+        test_directory: String,
+        test_group: Option<String>,
+    ) -> bool
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let name = self.label.clone();
+
+        let (emulator_result, reference_result) = match self.execute_differential(
+            summary.clone(),
+            &mut emulator,
+            &mut reference,
+            test_name.as_str(),
+            test_directory.as_str(),
+            test_group.as_deref(),
+        ) {
+            Some(results) => results,
+            None => return false,
+        };
+
+        // The emulator does not transfer the fees to the coinbase
+        let coinbase = Self::system_context(&self.env, &self.transaction).coinbase;
+        let differential = Differential::compare(
+            &emulator_result,
+            &emulator.get_state(),
+            &reference_result,
+            &reference.get_state(),
+            self.deployed_address(),
+            &[coinbase],
+        );
+
+        if differential.is_empty() {
+            Summary::passed_runtime(
+                summary,
+                format!("{test_name}: {name}"),
+                test_directory.clone(),
+                test_group,
+                emulator_result.cycles,
+                emulator_result.ergs,
+                emulator_result.gas,
+            );
+            false
+        } else {
+            Summary::failed(
+                summary,
+                format!("{test_name}: {name}"),
+                test_directory.clone(),
+                test_group.clone(),
+                differential.category(),
+                emulator_result.output.exception,
+                Some(differential.reference_description()),
+                Some(differential.emulator_description()),
+                self.transaction.data.0,
+                Fingerprint::failed(differential.category(), Some(&emulator_result.output), None),
+            );
+            true
+        }
+    }
+
+    ///
+    /// Runs the case on both the emulator and the reference EVM, recording the gas used
+    /// by both into the gas equivalence report.
+    ///
+    pub fn run_gas_report<E, R>(
+        self,
+        summary: Arc<Mutex<Summary>>,
+        gas_report: Arc<Mutex<GasReport>>,
+        mut emulator: E,
+        mut reference: R,
+        test_name: String,
+        test_directory: String,
+        test_group: Option<String>,
+    ) where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let name = self.label.clone();
+
+        // The emulator does not report the gas used by deploy transactions
+        if self.transaction.to.0.is_none() {
+            Summary::ignored(
+                summary,
+                format!("{test_name}: {name}"),
+                test_directory.clone(),
+                test_group.clone(),
+                IgnoreReason::other("Deploy transaction gas is not reported"),
+            );
+            return;
+        }
+
+        let (emulator_result, reference_result) = match self.execute_differential(
+            summary.clone(),
+            &mut emulator,
+            &mut reference,
+            test_name.as_str(),
+            test_directory.as_str(),
+            test_group.as_deref(),
+        ) {
+            Some(results) => results,
+            None => return,
+        };
+
+        let (emulator_gas, reference_gas) = match (
+            u64::try_from(emulator_result.gas),
+            u64::try_from(reference_result.gas),
+        ) {
+            (Ok(emulator_gas), Ok(reference_gas)) => (emulator_gas, reference_gas),
+            _ => {
+                Summary::invalid(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory.clone(),
+                    test_group.clone(),
+                    format!(
+                        "The gas used exceeds 64 bits: {} on the emulator, {} on the reference EVM",
+                        emulator_result.gas, reference_result.gas,
+                    ),
+                    self.transaction.data.0,
+                );
+                return;
+            }
+        };
+        GasReport::push(
+            gas_report,
+            format!("{test_name}: {name}"),
+            test_directory.clone(),
+            emulator_gas,
+            reference_gas,
+        );
+
+        if emulator_result.gas == reference_result.gas {
+            Summary::passed_runtime(
+                summary,
+                format!("{test_name}: {name}"),
+                test_directory.clone(),
+                test_group,
+                emulator_result.cycles,
+                emulator_result.ergs,
+                emulator_result.gas,
+            );
+        } else {
+            Summary::failed(
+                summary,
+                format!("{test_name}: {name}"),
+                test_directory.clone(),
+                test_group.clone(),
+                Category::Gas,
+                emulator_result.output.exception,
+                Some(format!("Gas used: {}", reference_result.gas)),
+                Some(emulator_result.gas.to_string()),
+                self.transaction.data.0,
+                Fingerprint::failed(Category::Gas, Some(&emulator_result.output), None),
             );
         }
+    }
+
+    ///
+    /// Reruns the case at the gas limits around the minimum passing gas on both the emulator and
+    /// the reference EVM, reporting the first limit where their success or state differs.
+    ///
+    pub fn run_gas_sweep<E, R>(
+        self,
+        summary: Arc<Mutex<Summary>>,
+        emulator: Arc<E>,
+        reference: Arc<R>,
+        evm_version: Option<EVMVersion>,
+        test_name: String,
+        test_directory: String,
+        test_group: Option<String>,
+    ) where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let name = self.label.clone();
+
+        let gas_sweep = match GasSweep::run(&self, emulator, reference, evm_version) {
+            Ok(Some(gas_sweep)) => gas_sweep,
+            Ok(None) => {
+                Summary::ignored(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory.clone(),
+                    test_group.clone(),
+                    IgnoreReason::other("Not supported by the emulator"),
+                );
+                return;
+            }
+            Err(error) => {
+                Summary::invalid(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory.clone(),
+                    test_group.clone(),
+                    error,
+                    self.transaction.data.0,
+                );
+                return;
+            }
+        };
 
+        match gas_sweep.divergence {
+            None => Summary::passed_special(
+                summary,
+                format!("{test_name}: {name}"),
+                test_directory.clone(),
+                test_group,
+            ),
+            Some((gas_limit, differential)) => {
+                Summary::failed(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory.clone(),
+                    test_group.clone(),
+                    differential.category(),
+                    false,
+                    Some(format!(
+                        "Gas limit {gas_limit}, minimum passing gas: {}\n   {}",
+                        Self::gas_limit_description(gas_sweep.reference_minimum),
+                        differential.reference_description()
+                    )),
+                    Some(format!(
+                        "minimum passing gas: {}\n   {}",
+                        Self::gas_limit_description(gas_sweep.emulator_minimum),
+                        differential.emulator_description()
+                    )),
+                    self.transaction.data.0,
+                    Fingerprint::failed(differential.category(), None, None),
+                );
+            }
+        }
+    }
+
+    ///
+    /// Executes the case on both the emulator and the reference EVM.
+    ///
+    /// Returns `None` if the case has been reported as invalid or ignored.
+    ///
+    fn execute_differential<E, R>(
+        &self,
+        summary: Arc<Mutex<Summary>>,
+        emulator: &mut E,
+        reference: &mut R,
+        test_name: &str,
+        test_directory: &str,
+        test_group: Option<&str>,
+    ) -> Option<(ExecutionResult, ExecutionResult)>
+    where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        let name = self.label.clone();
+
+        let emulator_result = self.execute(emulator);
+        let reference_result = self.execute(reference);
+
+        match (emulator_result, reference_result) {
+            (Ok(emulator_result), _) if emulator_result.output.system_error.is_some() => {
+                Summary::ignored(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory.to_owned(),
+                    test_group.map(str::to_owned),
+                    Self::ignore_reason(
+                        emulator_result.output.system_error.expect("Always exists"),
+                    ),
+                );
+                None
+            }
+            (Ok(emulator_result), Ok(reference_result)) => {
+                Some((emulator_result, reference_result))
+            }
+            (Err(error), _) => {
+                Summary::invalid(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory.to_owned(),
+                    test_group.map(str::to_owned),
+                    error,
+                    self.transaction.data.0.clone(),
+                );
+                None
+            }
+            (_, Err(error)) => {
+                Summary::invalid(
+                    summary,
+                    format!("{test_name}: {name}"),
+                    test_directory.to_owned(),
+                    test_group.map(str::to_owned),
+                    format!("Reference EVM: {error}"),
+                    self.transaction.data.0.clone(),
+                );
+                None
+            }
+        }
+    }
+
+    ///
+    /// Populates the prestate and executes the transaction on the specified VM.
+    ///
+    pub fn execute<V>(&self, vm: &mut V) -> anyhow::Result<ExecutionResult>
+    where
+        V: StateTestVm,
+    {
+        self.populate_prestate(vm);
+
+        let system_context = Self::system_context(&self.env, &self.transaction);
+
+        vm.execute(self.label.clone(), &self.transaction, &system_context)
+    }
+
+    ///
+    /// Executes the case on the specified VM and returns how it fails: the expected value of the
+    /// first mismatching expectation or the execution error.
+    ///
+    /// Returns `None` if the case passes or is not supported by the VM.
+    ///
+    pub fn failure<V>(&self, vm: &mut V) -> Option<String>
+    where
+        V: StateTestVm,
+    {
+        match self.execute(vm) {
+            Ok(result) if result.output.system_error.is_some() => None,
+            Ok(_) => Self::check_expected_state(&self.expected_state, vm)
+                .map(|mismatch| mismatch.expected_description()),
+            Err(error) => Some(error.to_string()),
+        }
+    }
+
+    ///
+    /// Populates the prestate and executes the transaction on the specified VM, also returning
+    /// the EIP-3155 trace.
+    ///
+    /// The name must be unique across the test suite, as the VM may keep its own trace files.
+    ///
+    pub fn execute_traced<V>(
+        &self,
+        vm: &mut V,
+        name: String,
+    ) -> anyhow::Result<(ExecutionResult, Trace)>
+    where
+        V: StateTestVm,
+    {
+        self.populate_prestate(vm);
+
+        let system_context = Self::system_context(&self.env, &self.transaction);
+
+        vm.execute_traced(name, &self.transaction, &system_context)
+    }
+
+    ///
+    /// Returns the block and transaction context of the case.
+    ///
+    pub fn context(&self) -> EVMContext {
+        Self::system_context(&self.env, &self.transaction)
+    }
+
+    ///
+    /// Populates the prestate accounts on the specified VM.
+    ///
+    fn populate_prestate<V>(&self, vm: &mut V)
+    where
+        V: StateTestVm,
+    {
+        for (address, state) in self.prestate.iter() {
+            vm.set_balance(*address, state.balance);
+            vm.set_nonce(*address, state.nonce);
+            vm.set_code(*address, state.code.0.clone());
+            vm.populate_storage(Self::prestate_storage(*address, &state.storage));
+        }
+    }
+
+    ///
+    /// Describes why the case is ignored by the emulator system error.
+    ///
+    fn ignore_reason(system_error: (usize, usize)) -> IgnoreReason {
+        match system_error.0 {
+            1 => IgnoreReason::unsupported_opcode(system_error.1 as u8),
+            2 => IgnoreReason::unsupported_precompile(system_error.1),
+            _ => panic!("Invalid system error type: {:?}", system_error),
+        }
+    }
+
+    ///
+    /// Describes the minimum passing gas limit found by the gas sweep.
+    ///
+    fn gas_limit_description(gas_limit: Option<u64>) -> String {
+        match gas_limit {
+            Some(gas_limit) => gas_limit.to_string(),
+            None => "none".to_owned(),
+        }
+    }
+
+    ///
+    /// Converts the prestate account storage into the VM storage values.
+    ///
+    fn prestate_storage(
+        address: web3::types::Address,
+        storage: &HashMap<web3::types::U256, web3::types::U256>,
+    ) -> HashMap<(web3::types::Address, web3::types::U256), web3::types::H256> {
+        storage
+            .iter()
+            .map(|(storage_key, storage_value)| {
+                ((address, *storage_key), utils::u256_to_h256(storage_value))
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the address of the contract created by the transaction, `None` for calls.
+    ///
+    pub fn deployed_address(&self) -> Option<web3::types::Address> {
+        match self.transaction.to.0 {
+            Some(_) => None,
+            None => Some(EVMAddressIterator::address_by_nonce(
+                &self.transaction.sender.unwrap_or_default(),
+                &self.transaction.nonce,
+            )),
+        }
+    }
+
+    ///
+    /// Builds the block and transaction context of the case.
+    ///
+    fn system_context(env: &EnvSection, transaction: &Transaction) -> EVMContext {
         let mut system_context = SystemContext::default_context(era_compiler_common::Target::EVM);
 
-        system_context.block_number = self.env.current_number.try_into().unwrap();
-        system_context.block_timestamp = self.env.current_timestamp.try_into().unwrap();
-        system_context.coinbase = self.env.current_coinbase;
-        system_context.block_gas_limit = self.env.current_gas_limit;
+        system_context.block_number = env.current_number.try_into().unwrap();
+        system_context.block_timestamp = env.current_timestamp.try_into().unwrap();
+        system_context.coinbase = env.current_coinbase;
+        system_context.block_gas_limit = env.current_gas_limit;
 
-        if let Some(gas_price) = self.transaction.gas_price {
+        if let Some(gas_price) = transaction.gas_price {
             system_context.gas_price = gas_price;
-        } else if let Some(base_fee) = self.env.current_base_fee {
+        } else if let Some(base_fee) = env.current_base_fee {
             let mut gas_price = base_fee;
 
-            if let Some(max_priority_fee) = self.transaction.max_priority_fee_per_gas {
+            if let Some(max_priority_fee) = transaction.max_priority_fee_per_gas {
                 gas_price += max_priority_fee;
             }
 
             system_context.gas_price = gas_price;
         }
 
-        if let Some(base_fee) = self.env.current_base_fee {
+        if let Some(base_fee) = env.current_base_fee {
             system_context.base_fee = base_fee;
         }
 
-        if let Some(current_difficulty) = self.env.current_difficulty {
+        if let Some(current_difficulty) = env.current_difficulty {
             system_context.block_difficulty = utils::u256_to_h256(&current_difficulty);
         }
 
-        if let Some(random) = self.env.current_random {
+        if let Some(random) = env.current_random {
             system_context.block_difficulty = utils::u256_to_h256(&random);
         }
 
-        let run_result = if self.transaction.to.0.is_none() {
-            vm.deploy_evm::<M>(
-                name.clone(),
-                self.transaction.sender.unwrap(),
-                self.transaction.data.0.clone(),
-                Some(self.transaction.value.as_u128()),
-                Some(self.transaction.gas_limit),
-                Some(system_context),
-            )
-        } else {
-            vm.execute_evm_interpreter::<M>(
-                name.clone(),
-                self.transaction.to.0.unwrap(),   // TODO deploy tx
-                self.transaction.sender.unwrap(), // TODO derive sender
-                Some(self.transaction.value.as_u128()), // TODO check overflow
-                Some(self.transaction.gas_limit),
-                self.transaction.data.0.clone(),
-                None,
-                Some(system_context),
-            )
-        };
+        system_context
+    }
 
-        let mut check_successful = true;
-        let mut expected: Option<String> = None;
-        let mut actual: Option<String> = None;
-        // TODO merge with prestate!
-        for (address, filler_struct) in self.expected_state {
+    ///
+    /// Checks that the executed transaction is not expected to be rejected by the test.
+    ///
+    fn check_expected_exception(&self) -> Option<Mismatch> {
+        let expect_exception = self.post_state.as_ref()?.expect_exception.as_ref()?;
+        Some(Mismatch::new(
+            Mismatch::FIELD_EXCEPTION,
+            web3::types::Address::zero(),
+            None,
+            expect_exception.to_owned(),
+            Some("The transaction has been executed".to_owned()),
+        ))
+    }
+
+    ///
+    /// Checks the post-state root against the test one, if the check is enabled.
+    ///
+    fn check_state_root<V>(&self, vm: &V, options: &RunOptions) -> Option<Mismatch>
+    where
+        V: StateTestVm,
+    {
+        if !options.state_root {
+            return None;
+        }
+        let expected = self.post_state.as_ref()?.hash;
+        let actual = StateRoot::compute(&vm.get_state());
+        if actual == expected {
+            return None;
+        }
+        Some(Mismatch::new(
+            Mismatch::FIELD_STATE_ROOT,
+            web3::types::Address::zero(),
+            None,
+            format!("{expected:?}"),
+            Some(format!("{actual:?}")),
+        ))
+    }
+
+    ///
+    /// Checks the post-state against the expected one.
+    ///
+    /// Returns the first mismatch.
+    ///
+    fn check_expected_state<V>(
+        expected_state: &HashMap<web3::types::Address, AccountFillerStruct>,
+        vm: &V,
+    ) -> Option<Mismatch>
+    where
+        V: StateTestVm,
+    {
+        for (&address, filler_struct) in expected_state.iter() {
             if filler_struct.balance.is_some() {
                 let expected_balance = filler_struct.balance.as_ref().unwrap();
                 if let Some(expected_balance_value) = expected_balance.as_value() {
-                    if vm.get_balance(address) != expected_balance_value {
-                        expected = Some(format!(
-                            "Balance of {address:?}: {:?}",
-                            expected_balance_value
+                    let actual_balance = vm.get_balance(address);
+                    if actual_balance != expected_balance_value {
+                        return Some(Mismatch::new(
+                            Mismatch::FIELD_BALANCE,
+                            address,
+                            None,
+                            format!("{expected_balance_value:?}"),
+                            Some(actual_balance.to_string()),
                         ));
-                        actual = Some(vm.get_balance(address).to_string());
-                        check_successful = false;
-                        break;
                     }
                 }
             }
@@ -314,12 +947,15 @@ impl Case {
             if filler_struct.nonce.is_some() {
                 let expected_nonce = filler_struct.nonce.as_ref().unwrap();
                 if let Some(expected_nonce_value) = expected_nonce.as_value() {
-                    if vm.get_nonce(address) != expected_nonce_value {
-                        expected =
-                            Some(format!("Nonce of {address:?}: {:?}", expected_nonce_value));
-                        actual = Some(vm.get_nonce(address).to_string());
-                        check_successful = false;
-                        break;
+                    let actual_nonce = vm.get_nonce(address);
+                    if actual_nonce != expected_nonce_value {
+                        return Some(Mismatch::new(
+                            Mismatch::FIELD_NONCE,
+                            address,
+                            None,
+                            format!("{expected_nonce_value:?}"),
+                            Some(actual_nonce.to_string()),
+                        ));
                     }
                 }
             }
@@ -327,17 +963,19 @@ impl Case {
             if filler_struct.code.is_some() {
                 let actual_code = vm.get_code(address).unwrap_or_default();
 
-                if actual_code != filler_struct.code.as_ref().unwrap().0 .0 {
-                    expected = Some(format!("Code of {address:?} is invalid"));
-                    actual = None;
-
-                    check_successful = false;
-                    break;
+                let expected_code = filler_struct.code.as_ref().unwrap().0 .0.as_slice();
+                if actual_code != expected_code {
+                    return Some(Mismatch::new(
+                        Mismatch::FIELD_CODE,
+                        address,
+                        None,
+                        format!("0x{}", hex::encode(expected_code)),
+                        Some(format!("0x{}", hex::encode(actual_code))),
+                    ));
                 }
             }
 
             if filler_struct.storage.is_some() {
-                let mut has_storage_divergence = false;
                 let storage =
                     AccountFillerStruct::parse_storage(filler_struct.storage.as_ref().unwrap());
                 for (key, _) in &storage {
@@ -353,95 +991,31 @@ impl Case {
                         U256Parsed::Value(expected_u256) => {
                             let unwrapped_actual_value = actual_value.unwrap_or_default(); // TODO check tests logic
                             if unwrapped_actual_value != utils::u256_to_h256(&expected_u256) {
-                                expected = Some(format!(
-                                    "Storage of {address:?}, {:?}: {:?}",
-                                    key.as_value().unwrap(),
-                                    utils::u256_to_h256(&expected_u256)
+                                return Some(Mismatch::new(
+                                    Mismatch::FIELD_STORAGE,
+                                    address,
+                                    Some(key_u256),
+                                    format!("{:?}", utils::u256_to_h256(&expected_u256)),
+                                    Some(format!("{:?}", actual_value)),
                                 ));
-                                actual = Some(format!("{:?}", actual_value));
-
-                                has_storage_divergence = true;
-                                break;
                             }
                         }
                         U256Parsed::Any => {
                             if actual_value.is_none() {
-                                expected = Some(format!(
-                                    "Storage of {address:?}, {:?}: {:?}",
-                                    key.as_value().unwrap(),
-                                    "Any value"
+                                return Some(Mismatch::new(
+                                    Mismatch::FIELD_STORAGE,
+                                    address,
+                                    Some(key_u256),
+                                    format!("{:?}", "Any value"),
+                                    Some("None".to_string()),
                                 ));
-                                actual = Some("None".to_string());
-
-                                has_storage_divergence = true;
-                                break;
                             }
                         }
                     };
                 }
-                if has_storage_divergence {
-                    check_successful = false;
-                    break;
-                }
             }
         }
 
-        if let Ok(res) = run_result {
-            //println!("GAS USED: {:?}", res.gas);
-            if let Some(system_error) = res.output.system_error {
-                match system_error.0 {
-                    1 => {
-                        // forbidden opcode
-                        //println!("{test_name}: {name}: FORBIDDEN OPCODE: {:#0x}", system_error.1)
-                    }
-                    2 => {
-                        // forbidden precompile
-                        //println!("{test_name}: {name}: FORBIDDEN PRECOMPILE: {:#0x}", system_error.1)
-                    }
-                    _ => {
-                        panic!("Invalid system error type: {:?}", system_error)
-                    }
-                }
-
-                Summary::ignored(summary, name);
-                return;
-            }
-            /*if res.output.exception {
-                Summary::failed(
-                    summary,
-                    format!("{test_name}: {name}"),
-                    Some("Finish successfully".to_string()),
-                    Some("Failed with exception".to_string()),
-                    self.transaction.data.0
-                );
-            } else {*/
-            if check_successful {
-                Summary::passed_runtime(
-                    summary,
-                    format!("{test_name}: {name}"),
-                    test_group,
-                    res.cycles,
-                    res.ergs,
-                    res.gas,
-                );
-            } else {
-                Summary::failed(
-                    summary,
-                    format!("{test_name}: {name}"),
-                    res.output.exception,
-                    expected,
-                    actual,
-                    self.transaction.data.0,
-                );
-            }
-            //}
-        } else {
-            Summary::invalid(
-                summary,
-                format!("{test_name}: {name}"),
-                run_result.err().unwrap(),
-                self.transaction.data.0,
-            );
-        }
+        None
     }
 }
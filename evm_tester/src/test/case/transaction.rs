@@ -1,4 +1,4 @@
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Clone, Copy)]
 pub struct FieldTo(pub Option<web3::types::Address>);
@@ -28,7 +28,26 @@ impl<'de> Deserialize<'de> for FieldTo {
     }
 }
 
-#[derive(Debug, Deserialize)]
+impl Serialize for FieldTo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Some(address) => serializer.serialize_str(format!("{address:?}").as_str()),
+            None => serializer.serialize_str(""),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    pub address: web3::types::Address,
+    pub storage_keys: Vec<web3::types::H256>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
     pub data: web3::types::Bytes,
@@ -41,4 +60,6 @@ pub struct Transaction {
     pub to: FieldTo,
     pub sender: Option<web3::types::Address>,
     pub value: web3::types::U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<Vec<AccessListItem>>,
 }
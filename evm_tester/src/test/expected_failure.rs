@@ -0,0 +1,99 @@
+//!
+//! The expected test failure.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The expected test failure.
+///
+/// The covered cases are still run, but their failures do not fail the run, and their passes
+/// are reported as unexpected.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectedFailure {
+    /// The expected failing case labels. All cases are expected to fail if not specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cases: Option<Vec<String>>,
+    /// The reason of the failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The tracking issue URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl ExpectedFailure {
+    ///
+    /// Whether the case with the specified label is expected to fail.
+    ///
+    pub fn covers(&self, label: &str) -> bool {
+        match self.cases.as_ref() {
+            Some(cases) => cases.iter().any(|case| case == label),
+            None => true,
+        }
+    }
+
+    ///
+    /// Describes the expected failure with its reason and tracking URL.
+    ///
+    pub fn description(&self) -> String {
+        match (self.reason.as_deref(), self.url.as_deref()) {
+            (Some(reason), Some(url)) => format!("{reason} ({url})"),
+            (Some(reason), None) => reason.to_owned(),
+            (None, Some(url)) => url.to_owned(),
+            (None, None) => "no reason specified".to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExpectedFailure;
+
+    ///
+    /// Returns the expected failure of the specified cases.
+    ///
+    fn expected_failure(cases: Option<&[&str]>) -> ExpectedFailure {
+        ExpectedFailure {
+            cases: cases.map(|cases| cases.iter().map(|case| (*case).to_owned()).collect()),
+            reason: None,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn covers_exact_case() {
+        let expected_failure = expected_failure(Some(&["0", "transfer"]));
+        assert!(expected_failure.covers("0"));
+        assert!(expected_failure.covers("transfer"));
+    }
+
+    #[test]
+    fn covers_all_cases_if_not_specified() {
+        let expected_failure = expected_failure(None);
+        assert!(expected_failure.covers("0"));
+        assert!(expected_failure.covers("transfer"));
+        assert!(expected_failure.covers(""));
+    }
+
+    #[test]
+    fn does_not_cover_other_cases() {
+        let expected_failure = expected_failure(Some(&["0", "transfer"]));
+        assert!(!expected_failure.covers("1"));
+        assert!(!expected_failure.covers("00"));
+        assert!(!expected_failure.covers("Transfer"));
+        assert!(!expected_failure.covers("transfer "));
+        assert!(!expected_failure(Some(&[])).covers("0"));
+    }
+
+    #[test]
+    fn deserialized_without_cases_covers_all() {
+        let expected_failure: ExpectedFailure =
+            serde_json::from_str(r#"{ "reason": "Not implemented" }"#).expect("Always valid");
+        assert!(expected_failure.covers("0"));
+        assert_eq!(expected_failure.description(), "Not implemented");
+    }
+}
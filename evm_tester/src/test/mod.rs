@@ -3,22 +3,29 @@
 //!
 
 pub mod case;
+pub mod expected_failure;
 pub mod filler_structure;
+pub mod run_options;
 pub mod test_structure;
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 
 use era_compiler_common::EVMVersion;
 use filler_structure::FillerStructure;
 use regex::Regex;
 use test_structure::TestStructure;
 
+use crate::gas_report::GasReport;
+use crate::summary::element::outcome::ignore_reason::IgnoreReason;
 use crate::summary::Summary;
 use crate::test::case::Case;
-use crate::vm::eravm::deployers::EraVMDeployer;
-use crate::vm::eravm::EraVM;
+use crate::test::expected_failure::ExpectedFailure;
+use crate::test::run_options::RunOptions;
+use crate::vm::state_test_vm::StateTestVm;
 
 fn wrap_numbers_in_quotes(input: &str) -> String {
     // Match numbers not already inside quotes
@@ -42,6 +49,8 @@ fn wrap_numbers_in_quotes(input: &str) -> String {
 pub struct Test {
     /// The test name.
     pub name: String,
+    /// The test directory relative to the test suite root.
+    pub directory: String,
     /// The test cases.
     pub cases: Vec<Case>,
     /// The test group.
@@ -50,6 +59,10 @@ pub struct Test {
     evm_version: Option<EVMVersion>,
     skipped_calldatas: Option<Vec<web3::types::Bytes>>,
     skipped_cases: Option<Vec<String>>,
+    /// The expected failures of the test cases.
+    expected_failures: Vec<ExpectedFailure>,
+    /// The time spent reading and parsing the test.
+    parse_time: Duration,
 }
 
 impl Test {
@@ -58,88 +71,283 @@ impl Test {
     ///
     pub fn new(
         name: String,
+        directory: String,
         cases: Vec<Case>,
         group: Option<String>,
         evm_version: Option<EVMVersion>,
         skipped_calldatas: Option<Vec<web3::types::Bytes>>,
         skipped_cases: Option<Vec<String>>,
+        expected_failures: Vec<ExpectedFailure>,
     ) -> Self {
         Self {
             name,
+            directory,
             cases,
             group,
             evm_version,
             skipped_calldatas,
             skipped_cases,
+            expected_failures,
+            parse_time: Duration::ZERO,
         }
     }
 
+    ///
+    /// Parses the test and its filler.
+    ///
     pub fn from_ethereum_test(
         str: &str,
         filler_str: &str,
         is_json: bool,
+        directory: String,
+        group: Option<String>,
         skipped_calldatas: Option<Vec<web3::types::Bytes>>,
         skipped_cases: Option<Vec<String>>,
-    ) -> Self {
+        expected_failures: Vec<ExpectedFailure>,
+    ) -> anyhow::Result<Self> {
         let cleaned_str = str.replace("0x:bigint ", "");
-        let test_structure: HashMap<String, TestStructure> =
-            serde_json::from_str(&cleaned_str).unwrap();
+        let test_structure: HashMap<String, TestStructure> = serde_json::from_str(&cleaned_str)
+            .map_err(|error| anyhow::anyhow!("Invalid test in `{directory}`: {error}"))?;
 
-        let keys: Vec<_> = test_structure.keys().collect();
-        let test_name = keys[0];
+        let (test_name, test_definition) = test_structure
+            .iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No test found in `{directory}`"))?;
 
         let test_filler_structure: HashMap<String, FillerStructure> = if is_json {
-            serde_json::from_str(filler_str).unwrap()
+            serde_json::from_str(filler_str)
+                .map_err(|error| anyhow::anyhow!("Invalid filler of `{test_name}`: {error}"))?
         } else {
             let wrapped_numbers = wrap_numbers_in_quotes(filler_str);
             //fs::write("out.yaml", wrapped_numbers.clone());
-            serde_yaml::from_str(&wrapped_numbers).unwrap()
+            serde_yaml::from_str(&wrapped_numbers)
+                .map_err(|error| anyhow::anyhow!("Invalid filler of `{test_name}`: {error}"))?
         };
 
-        let test_definition = test_structure.get(keys[0]).expect("Always exists");
-        let test_filler = test_filler_structure.get(keys[0]).expect("Always exists");
+        let test_filler = test_filler_structure
+            .get(test_name)
+            .ok_or_else(|| anyhow::anyhow!("Test `{test_name}` not found in its filler"))?;
 
         let cases = Case::from_ethereum_test(test_definition, test_filler);
 
-        Self {
+        Ok(Self {
             name: test_name.clone(),
+            directory,
             cases,
-            group: None,
+            group,
             evm_version: None,
             skipped_calldatas,
             skipped_cases,
+            expected_failures,
+            parse_time: Duration::ZERO,
+        })
+    }
+
+    ///
+    /// Sets the time spent reading and parsing the test.
+    ///
+    pub fn with_parse_time(mut self, parse_time: Duration) -> Self {
+        self.parse_time = parse_time;
+        self
+    }
+
+    ///
+    /// Runs the test on the specified VM.
+    ///
+    pub fn run<V>(self, summary: Arc<Mutex<Summary>>, vm: Arc<V>, options: &RunOptions)
+    where
+        V: StateTestVm,
+    {
+        self.run_cases(summary.clone(), |test, case| {
+            let vm = V::clone_for_case(vm.clone(), test.evm_version);
+            case.run(
+                summary.clone(),
+                vm,
+                test.name.clone(),
+                test.directory.clone(),
+                test.group.clone(),
+                options,
+            );
+        });
+    }
+
+    ///
+    /// Runs the test on both the emulator and the reference EVM, comparing the results.
+    ///
+    pub fn run_differential<E, R>(
+        self,
+        summary: Arc<Mutex<Summary>>,
+        emulator: Arc<E>,
+        reference: Arc<R>,
+    ) where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        self.run_cases(summary.clone(), |test, case| {
+            let emulator = E::clone_for_case(emulator.clone(), test.evm_version);
+            let reference = R::clone_for_case(reference.clone(), test.evm_version);
+            case.run_differential(
+                summary.clone(),
+                emulator,
+                reference,
+                test.name.clone(),
+                test.directory.clone(),
+                test.group.clone(),
+            );
+        });
+    }
+
+    ///
+    /// Runs the test on both the emulator and the reference EVM, recording the gas used
+    /// by both into the gas equivalence report.
+    ///
+    pub fn run_gas_report<E, R>(
+        self,
+        summary: Arc<Mutex<Summary>>,
+        gas_report: Arc<Mutex<GasReport>>,
+        emulator: Arc<E>,
+        reference: Arc<R>,
+    ) where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        self.run_cases(summary.clone(), |test, case| {
+            let emulator = E::clone_for_case(emulator.clone(), test.evm_version);
+            let reference = R::clone_for_case(reference.clone(), test.evm_version);
+            case.run_gas_report(
+                summary.clone(),
+                gas_report.clone(),
+                emulator,
+                reference,
+                test.name.clone(),
+                test.directory.clone(),
+                test.group.clone(),
+            );
+        });
+    }
+
+    ///
+    /// Sweeps the gas limits of the test cases on both the emulator and the reference EVM.
+    ///
+    pub fn run_gas_sweep<E, R>(
+        self,
+        summary: Arc<Mutex<Summary>>,
+        emulator: Arc<E>,
+        reference: Arc<R>,
+    ) where
+        E: StateTestVm,
+        R: StateTestVm,
+    {
+        self.run_cases(summary.clone(), |test, case| {
+            case.run_gas_sweep(
+                summary.clone(),
+                emulator.clone(),
+                reference.clone(),
+                test.evm_version,
+                test.name.clone(),
+                test.directory.clone(),
+                test.group.clone(),
+            );
+        });
+    }
+
+    ///
+    /// Returns the case with the specified label.
+    ///
+    pub fn into_case(mut self, label: &str) -> anyhow::Result<Case> {
+        let labels: Vec<String> = self.cases.iter().map(|case| case.label.clone()).collect();
+        match self.cases.iter().position(|case| case.label == label) {
+            Some(index) => Ok(self.cases.swap_remove(index)),
+            None => anyhow::bail!(
+                "Case `{label}` not found in `{}`, available cases: {}",
+                self.name,
+                labels.join(", ")
+            ),
         }
     }
 
     ///
-    /// Runs the test on EVM interpreter.
+    /// Returns the cases which are not skipped.
+    ///
+    pub fn into_enabled_cases(mut self) -> Vec<Case> {
+        std::mem::take(&mut self.cases)
+            .into_iter()
+            .filter(|case| !self.is_case_skipped(case))
+            .collect()
+    }
+
+    ///
+    /// Runs the enabled cases with `run_case`, reporting the skipped ones as ignored,
+    /// and adds the test time to the summary.
     ///
-    pub fn run_evm_interpreter<D, const M: bool>(self, summary: Arc<Mutex<Summary>>, vm: Arc<EraVM>)
+    fn run_cases<F>(mut self, summary: Arc<Mutex<Summary>>, mut run_case: F)
     where
-        D: EraVMDeployer,
+        F: FnMut(&Self, Case),
     {
-        for case in self.cases {
-            if let Some(filter_calldata) = self.skipped_calldatas.as_ref() {
-                if filter_calldata.contains(&case.transaction.data) {
-                    Summary::ignored(summary.clone(), case.label);
-                    continue;
-                }
+        let start = Instant::now();
+        for case in std::mem::take(&mut self.cases) {
+            if self.is_case_skipped(&case) {
+                Summary::ignored(
+                    summary.clone(),
+                    format!("{}: {}", self.name, case.label),
+                    self.directory.clone(),
+                    self.group.clone(),
+                    IgnoreReason::other("Skipped by the test filters"),
+                );
+                continue;
             }
+            self.start_case(summary.clone(), &case);
+            run_case(&self, case);
+        }
+        self.finish(summary, start);
+    }
+
+    ///
+    /// Starts timing the case and registers its expected failure in the summary, if any.
+    ///
+    fn start_case(&self, summary: Arc<Mutex<Summary>>, case: &Case) {
+        Summary::start_case(summary.clone(), format!("{}: {}", self.name, case.label));
+        if let Some(expected_failure) = self
+            .expected_failures
+            .iter()
+            .find(|expected_failure| expected_failure.covers(case.label.as_str()))
+        {
+            Summary::expect_failure(
+                summary,
+                format!("{}: {}", self.name, case.label),
+                expected_failure.clone(),
+            );
+        }
+    }
+
+    ///
+    /// Adds the test time, including the parsing, to the summary.
+    ///
+    fn finish(&self, summary: Arc<Mutex<Summary>>, start: Instant) {
+        Summary::test_time(
+            summary,
+            self.name.clone(),
+            self.directory.clone(),
+            self.parse_time + start.elapsed(),
+        );
+    }
 
-            if let Some(filter_cases) = self.skipped_cases.as_ref() {
-                if filter_cases.contains(&case.label) {
-                    Summary::ignored(summary.clone(), case.label);
-                    continue;
-                }
+    ///
+    /// Whether the case is skipped by its calldata or label.
+    ///
+    fn is_case_skipped(&self, case: &Case) -> bool {
+        if let Some(filter_calldata) = self.skipped_calldatas.as_ref() {
+            if filter_calldata.contains(&case.transaction.data) {
+                return true;
             }
+        }
 
-            let vm = EraVM::clone_with_contracts(vm.clone(), Default::default(), self.evm_version);
-            case.run_evm_interpreter::<D, M>(
-                summary.clone(),
-                vm,
-                self.name.clone(),
-                self.group.clone(),
-            );
+        if let Some(filter_cases) = self.skipped_cases.as_ref() {
+            if filter_cases.contains(&case.label) {
+                return true;
+            }
         }
+
+        false
     }
 }
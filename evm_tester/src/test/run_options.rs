@@ -0,0 +1,33 @@
+//!
+//! The test run options.
+//!
+
+use std::path::PathBuf;
+
+///
+/// The test run options.
+///
+/// Apart from the state root check, only affect what is written besides the summary.
+///
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Whether the EIP-3155 traces are written.
+    pub trace: bool,
+    /// The directory the failing cases are exported to as reproducers.
+    pub export_directory: Option<PathBuf>,
+    /// Whether the post-state root is checked against the test once the filler expectations match.
+    pub state_root: bool,
+}
+
+impl RunOptions {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(trace: bool, export_directory: Option<PathBuf>, state_root: bool) -> Self {
+        Self {
+            trace,
+            export_directory,
+            state_root,
+        }
+    }
+}
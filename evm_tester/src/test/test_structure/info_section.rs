@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct InfoSection {
     pub comment: String,
@@ -7,6 +7,7 @@ use pre_state::PreState;
 use transaction_section::TransactionSection;
 
 use serde::Deserialize;
+use serde::Serialize;
 
 pub mod env_section;
 pub mod info_section;
@@ -14,7 +15,7 @@ pub mod post_state;
 pub mod pre_state;
 pub mod transaction_section;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TestStructure {
     pub _info: InfoSection,
     pub env: EnvSection,
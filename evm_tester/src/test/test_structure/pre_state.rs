@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AccountState {
     pub balance: web3::types::U256,
     pub code: web3::types::Bytes,
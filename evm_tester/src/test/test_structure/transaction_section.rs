@@ -1,8 +1,10 @@
 use serde::Deserialize;
+use serde::Serialize;
 
+use crate::test::case::transaction::AccessListItem;
 use crate::test::case::transaction::FieldTo;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionSection {
     pub data: Vec<web3::types::Bytes>,
@@ -15,4 +17,6 @@ pub struct TransactionSection {
     pub to: FieldTo,
     pub sender: Option<web3::types::Address>,
     pub value: Vec<web3::types::U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_lists: Option<Vec<Option<Vec<AccessListItem>>>>,
 }
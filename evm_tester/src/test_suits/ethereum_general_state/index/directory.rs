@@ -7,6 +7,8 @@ use std::collections::BTreeMap;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::test::expected_failure::ExpectedFailure;
+
 use super::FSEntity;
 
 ///
@@ -26,6 +28,9 @@ pub struct Directory {
     pub skip_calldatas: Option<Vec<web3::types::Bytes>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skip_cases: Option<Vec<String>>,
+    /// The expected failure of the tests in the directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_failure: Option<ExpectedFailure>,
 }
 
 impl Directory {
@@ -39,6 +44,7 @@ impl Directory {
             comment: None,
             skip_calldatas: None,
             skip_cases: None,
+            expected_failure: None,
         }
     }
 }
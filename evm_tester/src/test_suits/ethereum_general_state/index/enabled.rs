@@ -4,6 +4,8 @@
 
 use std::path::PathBuf;
 
+use crate::test::expected_failure::ExpectedFailure;
+
 ///
 /// The enabled test entity description.
 ///
@@ -15,6 +17,8 @@ pub struct EnabledTest {
     pub group: Option<String>,
     pub skip_calldatas: Option<Vec<web3::types::Bytes>>,
     pub skip_cases: Option<Vec<String>>,
+    /// The expected failures of the test and its parent directories.
+    pub expected_failures: Vec<ExpectedFailure>,
 }
 
 impl EnabledTest {
@@ -26,12 +30,14 @@ impl EnabledTest {
         group: Option<String>,
         skip_calldatas: Option<Vec<web3::types::Bytes>>,
         skip_cases: Option<Vec<String>>,
+        expected_failures: Vec<ExpectedFailure>,
     ) -> Self {
         Self {
             path,
             group,
             skip_calldatas,
             skip_cases,
+            expected_failures,
         }
     }
 }
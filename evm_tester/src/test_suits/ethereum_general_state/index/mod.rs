@@ -17,6 +17,8 @@ use std::path::PathBuf;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::test::expected_failure::ExpectedFailure;
+
 use self::changes::Changes;
 use self::directory::Directory;
 use self::enabled::EnabledTest;
@@ -96,7 +98,7 @@ impl FSEntity {
     ///
     pub fn into_enabled_list(self, initial: &Path) -> Vec<EnabledTest> {
         let mut accumulator = Vec::with_capacity(16384);
-        self.into_enabled_list_recursive(initial, &mut accumulator, &vec![], &vec![]);
+        self.into_enabled_list_recursive(initial, &mut accumulator, &vec![], &vec![], &vec![]);
         accumulator.sort_by_key(|test| test.path.to_owned());
         accumulator
     }
@@ -107,6 +109,7 @@ impl FSEntity {
     pub fn into_enabled_test(self, initial: &Path, path: &Path) -> Option<EnabledTest> {
         let mut skipped_calldatas: Vec<web3::types::Bytes> = vec![];
         let mut skipped_cases: Vec<String> = vec![];
+        let mut expected_failures: Vec<ExpectedFailure> = vec![];
 
         let mut current_entity = self;
         for path_part in path.iter() {
@@ -120,6 +123,10 @@ impl FSEntity {
                         skipped_cases.extend(additional_skipped_cases);
                     }
 
+                    if let Some(expected_failure) = directory.expected_failure {
+                        expected_failures.push(expected_failure);
+                    }
+
                     current_entity = match directory
                         .entries
                         .remove(path_part.to_string_lossy().as_ref())
@@ -137,6 +144,9 @@ impl FSEntity {
                 if !file.enabled {
                     return None;
                 }
+                if let Some(expected_failure) = file.expected_failure {
+                    expected_failures.push(expected_failure);
+                }
                 let mut file_path = initial.to_path_buf();
                 file_path.push(path);
                 Some(EnabledTest::new(
@@ -144,6 +154,7 @@ impl FSEntity {
                     file.group,
                     Some(skipped_calldatas),
                     Some(skipped_cases),
+                    expected_failures,
                 ))
             }
         }
@@ -164,6 +175,7 @@ impl FSEntity {
                 new_file.enabled = old_file.enabled;
                 new_file.group = old_file.group.clone();
                 new_file.comment = old_file.comment.clone();
+                new_file.expected_failure = old_file.expected_failure.clone();
 
                 let new_hash = new_file
                     .hash
@@ -201,6 +213,7 @@ impl FSEntity {
                     comment: old_comment,
                     skip_calldatas: old_skip_calldatas,
                     skip_cases: old_skip_cases,
+                    expected_failure: old_expected_failure,
                 }),
                 Self::Directory(Directory {
                     enabled: new_enabled,
@@ -208,12 +221,14 @@ impl FSEntity {
                     comment: new_comment,
                     skip_calldatas: new_skip_calldatas,
                     skip_cases: new_skip_cases,
+                    expected_failure: new_expected_failure,
                 }),
             ) => {
                 *new_enabled = *old_enabled;
                 *new_comment = old_comment.clone();
                 *new_skip_calldatas = old_skip_calldatas.clone();
                 *new_skip_cases = old_skip_cases.clone();
+                *new_expected_failure = old_expected_failure.clone();
 
                 (old_entities, new_entities)
             }
@@ -263,9 +278,11 @@ impl FSEntity {
         accumulator: &mut Vec<EnabledTest>,
         skipped_calldatas: &Vec<web3::types::Bytes>,
         skipped_cases: &Vec<String>,
+        expected_failures: &Vec<ExpectedFailure>,
     ) {
         let mut skipped_calldatas_new = skipped_calldatas.clone();
         let mut skipped_cases_new = skipped_cases.clone();
+        let mut expected_failures_new = expected_failures.clone();
 
         let entries = match self {
             Self::File(file) => {
@@ -281,11 +298,16 @@ impl FSEntity {
                     skipped_cases_new.extend(additional_skipped_cases);
                 }
 
+                if let Some(expected_failure) = file.expected_failure {
+                    expected_failures_new.push(expected_failure);
+                }
+
                 accumulator.push(EnabledTest::new(
                     current.to_owned(),
                     file.group,
                     Some(skipped_calldatas_new),
                     Some(skipped_cases_new),
+                    expected_failures_new,
                 ));
                 return;
             }
@@ -302,6 +324,10 @@ impl FSEntity {
                     skipped_cases_new.extend(additional_skipped_cases);
                 }
 
+                if let Some(expected_failure) = directory.expected_failure {
+                    expected_failures_new.push(expected_failure);
+                }
+
                 directory.entries
             }
         };
@@ -314,6 +340,7 @@ impl FSEntity {
                 accumulator,
                 &skipped_calldatas_new,
                 &skipped_cases_new,
+                &expected_failures_new,
             );
         }
     }
@@ -10,6 +10,8 @@ use std::path::Path;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::test::expected_failure::ExpectedFailure;
+
 ///
 /// The Solidity test file.
 ///
@@ -34,6 +36,9 @@ pub struct TestFile {
     pub skip_calldatas: Option<Vec<web3::types::Bytes>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skip_cases: Option<Vec<String>>,
+    /// The expected failure of the test cases.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_failure: Option<ExpectedFailure>,
 }
 
 impl TryFrom<&Path> for TestFile {
@@ -57,6 +62,7 @@ impl TryFrom<&Path> for TestFile {
             comment: None,
             skip_calldatas: None,
             skip_cases: None,
+            expected_failure: None,
         })
     }
 }
@@ -4,6 +4,7 @@
 
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Instant;
 
 pub mod index;
 
@@ -32,6 +33,78 @@ impl EthereumGeneralStateTestsDirectory {
         let index: index::FSEntity = serde_yaml::from_str(index_data.as_str())?;
         Ok(index)
     }
+
+    ///
+    /// Reads a single test file, regardless of the index and filters.
+    ///
+    /// The path is resolved against the tests directory if it does not exist as is. The filler
+    /// is looked up in the fillers directory first, and then next to the test file, where the
+    /// reproducers keep it.
+    ///
+    pub fn read_test(
+        test_path: &Path,
+        directory_path: &Path,
+        filler_path: &Path,
+    ) -> anyhow::Result<Test> {
+        let test_path = if test_path.exists() {
+            test_path.to_path_buf()
+        } else {
+            directory_path.join(test_path)
+        };
+        let file = std::fs::read_to_string(test_path.as_path()).map_err(|error| {
+            anyhow::anyhow!("Test `{}` not found: {error}", test_path.to_string_lossy())
+        })?;
+
+        let file_name = test_path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("Invalid test path `{}`", test_path.to_string_lossy()))?
+            .to_string_lossy()
+            .to_string();
+        let test_name = remove_suffix(&file_name, ".json").to_string();
+
+        let dir_name = directory_path.file_name().unwrap_or_default();
+        let relative_path: PathBuf = if test_path.iter().any(|component| component == dir_name) {
+            test_path
+                .iter()
+                .skip_while(|component| *component != dir_name)
+                .skip(1)
+                .collect()
+        } else {
+            PathBuf::from(file_name.as_str())
+        };
+        let test_directory = relative_path
+            .parent()
+            .map(|parent| parent.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let filler_directory = filler_path.join(test_directory.as_str());
+        let sibling_directory = test_path.parent().unwrap_or(Path::new("."));
+        let filler_path_yml = filler_directory.join(format!("{test_name}Filler.yml"));
+        let filler_path_json = [
+            filler_directory.join(format!("{test_name}Filler.json")),
+            sibling_directory.join(format!("{test_name}Filler.json")),
+        ]
+        .into_iter()
+        .find(|path| path.exists());
+        let (filler_file, is_json) = if filler_path_yml.exists() {
+            (std::fs::read_to_string(filler_path_yml)?, false)
+        } else if let Some(filler_path_json) = filler_path_json {
+            (std::fs::read_to_string(filler_path_json)?, true)
+        } else {
+            anyhow::bail!("Filler of `{test_name}` not found");
+        };
+
+        Test::from_ethereum_test(
+            &file,
+            &filler_file,
+            is_json,
+            test_directory,
+            None,
+            None,
+            None,
+            Vec::new(),
+        )
+    }
 }
 
 impl Collection for EthereumGeneralStateTestsDirectory {
@@ -42,7 +115,7 @@ impl Collection for EthereumGeneralStateTestsDirectory {
     ) -> anyhow::Result<Vec<Test>> {
         let index_path = PathBuf::from(Self::INDEX_NAME);
 
-        Ok(Self::read_index(index_path.as_path())?
+        Self::read_index(index_path.as_path())?
             .into_enabled_list(directory_path)
             .into_iter()
             .filter_map(|test| {
@@ -56,6 +129,7 @@ impl Collection for EthereumGeneralStateTestsDirectory {
                     return None;
                 }
 
+                let start = Instant::now();
                 let file = std::fs::read_to_string(test.path.clone())
                     .unwrap_or_else(|_| panic!("Test not found: {:?}", test.path));
 
@@ -70,6 +144,11 @@ impl Collection for EthereumGeneralStateTestsDirectory {
                     .collect();
 
                 let test_name = remove_suffix(&file_name, ".json").to_string();
+                let test_directory = relative_path
+                    .parent()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string();
                 let filler_name_yml = test_name.clone() + "Filler.yml";
 
                 let filler_path = filler_path.join(relative_path.parent().unwrap());
@@ -93,15 +172,19 @@ impl Collection for EthereumGeneralStateTestsDirectory {
                     }
                 }
 
-                Some(Test::from_ethereum_test(
+                let test = Test::from_ethereum_test(
                     &file,
                     &filler_file,
                     is_json,
+                    test_directory,
+                    test.group,
                     test.skip_calldatas,
                     test.skip_cases,
-                ))
+                    test.expected_failures,
+                );
+                Some(test.map(|test| test.with_parse_time(start.elapsed())))
             })
-            .collect())
+            .collect()
     }
 }
 
@@ -0,0 +1,123 @@
+//!
+//! The EIP-3155 execution trace.
+//!
+
+pub mod quantity;
+pub mod step;
+pub mod trace_diff;
+pub mod trace_summary;
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::utils;
+use crate::vm::opcode;
+
+use self::step::Step;
+use self::trace_summary::TraceSummary;
+
+///
+/// The EIP-3155 execution trace.
+///
+/// Is written in the JSON lines format, one step per line followed by the summary line.
+///
+#[derive(Debug, Default, Clone)]
+pub struct Trace {
+    /// The executed instructions.
+    pub steps: Vec<Step>,
+    /// The transaction outcome.
+    pub summary: Option<TraceSummary>,
+}
+
+impl Trace {
+    /// The directory the traces are written to.
+    pub const DIRECTORY: &'static str = "./trace/eip3155/";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(steps: Vec<Step>, summary: Option<TraceSummary>) -> Self {
+        Self { steps, summary }
+    }
+
+    ///
+    /// Parses the trace from the JSON lines format.
+    ///
+    /// Lines which are neither steps nor the summary are skipped.
+    ///
+    pub fn from_json_lines(input: &str) -> anyhow::Result<Self> {
+        let mut trace = Self::default();
+        for (index, line) in input.lines().enumerate() {
+            let value: serde_json::Value = match serde_json::from_str(line) {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            if value.get("pc").is_some() {
+                let mut step: Step = serde_json::from_value(value).map_err(|error| {
+                    anyhow::anyhow!("Invalid trace step at line {}: {error}", index + 1)
+                })?;
+                if step.op_name.is_empty() {
+                    step.op_name = opcode::name(step.op).to_owned();
+                }
+                trace.steps.push(step);
+            } else if value.get("gasUsed").is_some() {
+                let summary = serde_json::from_value(value).map_err(|error| {
+                    anyhow::anyhow!("Invalid trace summary at line {}: {error}", index + 1)
+                })?;
+                trace.summary = Some(summary);
+            }
+        }
+        Ok(trace)
+    }
+
+    ///
+    /// Serializes the trace into the JSON lines format.
+    ///
+    pub fn to_json_lines(&self) -> anyhow::Result<String> {
+        let mut output = String::with_capacity(self.steps.len() * 128);
+        for step in self.steps.iter() {
+            output.push_str(serde_json::to_string(step)?.as_str());
+            output.push('\n');
+        }
+        if let Some(summary) = self.summary.as_ref() {
+            output.push_str(serde_json::to_string(summary)?.as_str());
+            output.push('\n');
+        }
+        Ok(output)
+    }
+
+    ///
+    /// Writes the trace to the specified directory, naming the file after the case.
+    ///
+    /// Returns the trace file path.
+    ///
+    pub fn write(&self, directory: &Path, name: &str) -> anyhow::Result<PathBuf> {
+        std::fs::create_dir_all(directory)?;
+
+        let path = directory.join(format!("{}.jsonl", utils::file_name(name)));
+        std::fs::write(&path, self.to_json_lines()?)?;
+
+        Ok(path)
+    }
+
+    ///
+    /// Derives the gas cost of every step from the gas left before the next step in the same
+    /// call frame, as the reconstructed traces only know the gas left.
+    ///
+    /// The cost of a call includes the gas spent by the callee. The last step of a frame is
+    /// left with a zero cost.
+    ///
+    pub fn fill_gas_costs(&mut self) {
+        for index in 0..self.steps.len() {
+            let depth = self.steps[index].depth;
+            let next = self.steps[index + 1..]
+                .iter()
+                .find(|step| step.depth <= depth)
+                .filter(|step| step.depth == depth)
+                .map(|step| step.gas);
+            if let Some(next_gas) = next {
+                self.steps[index].gas_cost = self.steps[index].gas.saturating_sub(next_gas);
+            }
+        }
+    }
+}
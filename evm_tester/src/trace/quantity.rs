@@ -0,0 +1,73 @@
+//!
+//! The EIP-3155 quantity serialization.
+//!
+
+use serde::Deserialize;
+
+///
+/// Serializes the quantity as a `0x`-prefixed hexadecimal string.
+///
+pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(format!("{value:#x}").as_str())
+}
+
+///
+/// Deserializes the quantity from either a number or a hexadecimal string, as the tracers
+/// disagree on the representation.
+///
+pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Quantity {
+        Number(u64),
+        String(String),
+    }
+
+    match Quantity::deserialize(deserializer)? {
+        Quantity::Number(value) => Ok(value),
+        Quantity::String(value) => match value.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => value.parse::<u64>(),
+        }
+        .map_err(serde::de::Error::custom),
+    }
+}
+
+///
+/// The optional EIP-3155 quantity serialization.
+///
+pub mod optional {
+    use serde::Deserialize;
+
+    ///
+    /// Serializes the quantity if it is known.
+    ///
+    pub fn serialize<S>(value: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match value {
+            Some(value) => super::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    ///
+    /// Deserializes the quantity, keeping `null` as unknown.
+    ///
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Quantity(#[serde(with = "super")] u64);
+
+        Ok(Option::<Quantity>::deserialize(deserializer)?.map(|Quantity(value)| value))
+    }
+}
@@ -0,0 +1,77 @@
+//!
+//! The EIP-3155 execution trace step.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::vm::opcode;
+
+///
+/// The EIP-3155 execution trace step.
+///
+/// Describes the state before the instruction is executed.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Step {
+    /// The program counter.
+    pub pc: u64,
+    /// The opcode.
+    pub op: u8,
+    /// The gas left before the instruction.
+    #[serde(with = "super::quantity")]
+    pub gas: u64,
+    /// The gas charged for the instruction.
+    #[serde(with = "super::quantity")]
+    pub gas_cost: u64,
+    /// The memory size in bytes.
+    #[serde(with = "super::quantity")]
+    pub mem_size: u64,
+    /// The stack, with the top item last.
+    pub stack: Vec<web3::types::U256>,
+    /// The call depth, starting from 1.
+    pub depth: u64,
+    /// The accumulated gas refund, which is not reported by the emulator.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "super::quantity::optional"
+    )]
+    pub refund: Option<u64>,
+    /// The opcode mnemonic, which is optional for some tracers.
+    #[serde(default)]
+    pub op_name: String,
+    /// The error the instruction has failed with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Step {
+    ///
+    /// A shortcut constructor.
+    ///
+    /// The gas cost is set to zero and the refund is left unknown.
+    ///
+    pub fn new(
+        pc: u64,
+        op: u8,
+        gas: u64,
+        mem_size: u64,
+        stack: Vec<web3::types::U256>,
+        depth: u64,
+    ) -> Self {
+        Self {
+            pc,
+            op,
+            gas,
+            gas_cost: 0,
+            mem_size,
+            stack,
+            depth,
+            refund: None,
+            op_name: opcode::name(op).to_owned(),
+            error: None,
+        }
+    }
+}
@@ -0,0 +1,377 @@
+//!
+//! The EIP-3155 trace diff.
+//!
+
+use serde::Serialize;
+
+use super::step::Step;
+use super::Trace;
+
+///
+/// The first divergence between the emulator and the reference EVM traces.
+///
+/// The steps are aligned per call frame by their call depth and program counter, so an extra step
+/// or frame on either side is left without a counterpart instead of shifting the rest of the traces.
+/// The position is reported as the row of the aligned traces, the step index in either trace, the
+/// call depth and the step within the frame.
+///
+#[derive(Debug, Serialize)]
+pub struct TraceDiff {
+    /// The diverging row of the aligned traces.
+    pub index: usize,
+    /// The diverging step index in the emulator trace, if the emulator has the step.
+    pub emulator_index: Option<usize>,
+    /// The diverging step index in the reference trace, if the reference has the step.
+    pub reference_index: Option<usize>,
+    /// The call depth of the diverging step.
+    pub depth: u64,
+    /// The diverging step index within its call frame.
+    pub frame_step: usize,
+    /// The diverging fields, e.g. `pc` or `gas`.
+    pub fields: Vec<&'static str>,
+    /// The surrounding aligned emulator and reference steps, starting from `context_start`.
+    pub context: Vec<(Option<Step>, Option<Step>)>,
+    /// The row of the first context step.
+    pub context_start: usize,
+}
+
+impl TraceDiff {
+    ///
+    /// Finds the first divergence in the call depth, program counter, opcode, gas left or
+    /// stack top, keeping `context` rows of the aligned traces before and after it.
+    ///
+    /// Returns `None` if the traces agree.
+    ///
+    pub fn compare(emulator: &Trace, reference: &Trace, context: usize) -> Option<Self> {
+        let rows = Self::align(emulator.steps.as_slice(), reference.steps.as_slice());
+        let mut frame_steps: Vec<usize> = Vec::new();
+
+        for (index, (emulator_index, reference_index)) in rows.iter().copied().enumerate() {
+            let emulator_step = emulator_index.map(|index| &emulator.steps[index]);
+            let reference_step = reference_index.map(|index| &reference.steps[index]);
+
+            // The frames deeper than the current step have returned, so their counters are reset
+            let depth = emulator_step
+                .or(reference_step)
+                .map(|step| step.depth as usize)
+                .unwrap_or_default()
+                .max(1);
+            frame_steps.resize(depth, 0);
+            let frame_step = frame_steps[depth - 1];
+            frame_steps[depth - 1] += 1;
+
+            let fields = match (emulator_step, reference_step) {
+                (Some(emulator_step), Some(reference_step)) => {
+                    Self::diverging_fields(emulator_step, reference_step)
+                }
+                // A missing step is a length mismatch once the other trace has ended
+                (Some(_), None) if rows[index..].iter().all(|(_, other)| other.is_none()) => {
+                    vec!["length"]
+                }
+                (None, Some(_)) if rows[index..].iter().all(|(other, _)| other.is_none()) => {
+                    vec!["length"]
+                }
+                _ => vec!["step"],
+            };
+            if fields.is_empty() {
+                continue;
+            }
+
+            let context_start = index.saturating_sub(context);
+            let context_end = (index + context + 1).min(rows.len());
+            let context = rows[context_start..context_end]
+                .iter()
+                .map(|(emulator_index, reference_index)| {
+                    (
+                        emulator_index.map(|index| emulator.steps[index].clone()),
+                        reference_index.map(|index| reference.steps[index].clone()),
+                    )
+                })
+                .collect();
+
+            return Some(Self {
+                index,
+                emulator_index,
+                reference_index,
+                depth: depth as u64,
+                frame_step,
+                fields,
+                context,
+                context_start,
+            });
+        }
+
+        None
+    }
+
+    ///
+    /// Aligns the steps of both traces per call frame, returning the step indices of every row.
+    ///
+    /// The steps at the same depth are paired. If one trace is deeper, its steps are left without
+    /// a counterpart until it returns to the depth of the other trace. If the program counters
+    /// differ and the next step of one trace matches the current step of the other, the current
+    /// step of the former is an extra one and is left without a counterpart as well.
+    ///
+    fn align(emulator: &[Step], reference: &[Step]) -> Vec<(Option<usize>, Option<usize>)> {
+        let mut rows = Vec::with_capacity(emulator.len().max(reference.len()));
+        let mut emulator_index = 0;
+        let mut reference_index = 0;
+
+        loop {
+            match (emulator.get(emulator_index), reference.get(reference_index)) {
+                (Some(emulator_step), Some(reference_step))
+                    if emulator_step.depth > reference_step.depth =>
+                {
+                    rows.push((Some(emulator_index), None));
+                    emulator_index += 1;
+                }
+                (Some(emulator_step), Some(reference_step))
+                    if emulator_step.depth < reference_step.depth =>
+                {
+                    rows.push((None, Some(reference_index)));
+                    reference_index += 1;
+                }
+                (Some(emulator_step), Some(reference_step))
+                    if emulator_step.pc != reference_step.pc
+                        && Self::is_same_position(
+                            emulator.get(emulator_index + 1),
+                            reference_step,
+                        ) =>
+                {
+                    rows.push((Some(emulator_index), None));
+                    emulator_index += 1;
+                }
+                (Some(emulator_step), Some(reference_step))
+                    if emulator_step.pc != reference_step.pc
+                        && Self::is_same_position(
+                            reference.get(reference_index + 1),
+                            emulator_step,
+                        ) =>
+                {
+                    rows.push((None, Some(reference_index)));
+                    reference_index += 1;
+                }
+                (Some(_), Some(_)) => {
+                    rows.push((Some(emulator_index), Some(reference_index)));
+                    emulator_index += 1;
+                    reference_index += 1;
+                }
+                (Some(_), None) => {
+                    rows.push((Some(emulator_index), None));
+                    emulator_index += 1;
+                }
+                (None, Some(_)) => {
+                    rows.push((None, Some(reference_index)));
+                    reference_index += 1;
+                }
+                (None, None) => break,
+            }
+        }
+
+        rows
+    }
+
+    ///
+    /// Whether the step is at the same call depth and program counter as the other one.
+    ///
+    fn is_same_position(step: Option<&Step>, other: &Step) -> bool {
+        step.map(|step| step.depth == other.depth && step.pc == other.pc)
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Returns the names of the compared fields which differ.
+    ///
+    fn diverging_fields(emulator: &Step, reference: &Step) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if emulator.depth != reference.depth {
+            fields.push("depth");
+        }
+        if emulator.pc != reference.pc {
+            fields.push("pc");
+        }
+        if emulator.op != reference.op {
+            fields.push("op");
+        }
+        if emulator.gas != reference.gas {
+            fields.push("gas");
+        }
+        if emulator.stack.last() != reference.stack.last() {
+            fields.push("stack top");
+        }
+        fields
+    }
+
+    ///
+    /// Formats the step index of either trace, which is absent for the steps without a counterpart.
+    ///
+    fn fmt_index(index: Option<usize>) -> String {
+        index
+            .map(|index| index.to_string())
+            .unwrap_or_else(|| "-".to_owned())
+    }
+
+    ///
+    /// Writes a single step row.
+    ///
+    fn fmt_step(
+        f: &mut std::fmt::Formatter<'_>,
+        marker: &str,
+        source: &str,
+        index: usize,
+        step: Option<&Step>,
+    ) -> std::fmt::Result {
+        match step {
+            Some(step) => writeln!(
+                f,
+                "{marker} {index:>6} {source:<9} {:>5} {:>6} {:<14} {:>12} {}",
+                step.depth,
+                step.pc,
+                step.op_name,
+                step.gas,
+                step.stack
+                    .last()
+                    .map(|value| format!("{value:#x}"))
+                    .unwrap_or_else(|| "-".to_owned()),
+            ),
+            None => writeln!(f, "{marker} {index:>6} {source:<9} -"),
+        }
+    }
+}
+
+impl std::fmt::Display for TraceDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "First divergence at row {} (emulator step {}, reference step {}, depth {}, step {} of the frame) in: {}",
+            self.index,
+            Self::fmt_index(self.emulator_index),
+            Self::fmt_index(self.reference_index),
+            self.depth,
+            self.frame_step,
+            self.fields.join(", "),
+        )?;
+        writeln!(
+            f,
+            "  {:>6} {:<9} {:>5} {:>6} {:<14} {:>12} stack top",
+            "row", "source", "depth", "pc", "op", "gas"
+        )?;
+        for (offset, (emulator, reference)) in self.context.iter().enumerate() {
+            let index = self.context_start + offset;
+            let marker = if index == self.index { ">" } else { " " };
+            Self::fmt_step(f, marker, "emulator", index, emulator.as_ref())?;
+            Self::fmt_step(f, marker, "reference", index, reference.as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trace::step::Step;
+    use crate::trace::Trace;
+
+    use super::TraceDiff;
+
+    fn step(pc: u64, gas: u64, depth: u64) -> Step {
+        Step::new(pc, 0x5b, gas, 0, vec![], depth)
+    }
+
+    #[test]
+    fn equal() {
+        let trace = Trace::new(vec![step(0, 100, 1), step(1, 99, 1)], None);
+
+        assert!(TraceDiff::compare(&trace, &trace, 2).is_none());
+    }
+
+    #[test]
+    fn extra_frame() {
+        let emulator = Trace::new(
+            vec![
+                step(0, 100, 1),
+                step(0, 50, 2),
+                step(1, 49, 2),
+                step(1, 90, 1),
+                step(2, 89, 1),
+            ],
+            None,
+        );
+        let reference = Trace::new(vec![step(0, 100, 1), step(1, 90, 1), step(2, 80, 1)], None);
+
+        let diff = TraceDiff::compare(&emulator, &reference, 1).expect("Always diverges");
+        assert_eq!(diff.index, 1);
+        assert_eq!(diff.emulator_index, Some(1));
+        assert_eq!(diff.reference_index, None);
+        assert_eq!(diff.fields, vec!["step"]);
+        assert_eq!(diff.context.len(), 3);
+        assert_eq!(diff.context[2], (Some(step(1, 49, 2)), None));
+    }
+
+    #[test]
+    fn extra_step() {
+        let emulator = Trace::new(
+            vec![
+                step(0, 100, 1),
+                step(1, 99, 1),
+                step(2, 98, 1),
+                step(3, 97, 1),
+            ],
+            None,
+        );
+        let reference = Trace::new(vec![step(0, 100, 1), step(2, 98, 1), step(3, 97, 1)], None);
+
+        let diff = TraceDiff::compare(&emulator, &reference, 2).expect("Always diverges");
+        assert_eq!(diff.index, 1);
+        assert_eq!(diff.emulator_index, Some(1));
+        assert_eq!(diff.reference_index, None);
+        assert_eq!(diff.fields, vec!["step"]);
+        assert_eq!(
+            diff.context[3],
+            (Some(step(3, 97, 1)), Some(step(3, 97, 1)))
+        );
+    }
+
+    #[test]
+    fn realigned_after_frame() {
+        let emulator = Trace::new(
+            vec![
+                step(0, 100, 1),
+                step(0, 50, 2),
+                step(1, 90, 1),
+                step(2, 89, 1),
+            ],
+            None,
+        );
+        let reference = Trace::new(
+            vec![
+                step(0, 100, 1),
+                step(0, 50, 2),
+                step(1, 49, 2),
+                step(1, 90, 1),
+                step(2, 89, 1),
+            ],
+            None,
+        );
+
+        let diff = TraceDiff::compare(&emulator, &reference, 2).expect("Always diverges");
+        assert_eq!(diff.index, 2);
+        assert_eq!(diff.emulator_index, None);
+        assert_eq!(diff.reference_index, Some(2));
+        assert_eq!(diff.depth, 2);
+        assert_eq!(diff.frame_step, 1);
+        assert_eq!(
+            diff.context[3],
+            (Some(step(1, 90, 1)), Some(step(1, 90, 1)))
+        );
+    }
+
+    #[test]
+    fn length() {
+        let emulator = Trace::new(vec![step(0, 100, 1)], None);
+        let reference = Trace::new(vec![step(0, 100, 1), step(1, 99, 1)], None);
+
+        let diff = TraceDiff::compare(&emulator, &reference, 0).expect("Always diverges");
+        assert_eq!(diff.index, 1);
+        assert_eq!(diff.fields, vec!["length"]);
+    }
+}
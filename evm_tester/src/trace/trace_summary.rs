@@ -0,0 +1,40 @@
+//!
+//! The EIP-3155 execution trace summary.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The EIP-3155 execution trace summary.
+///
+/// Is written as the last line of the trace.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceSummary {
+    /// The return data as a hexadecimal string.
+    pub output: String,
+    /// The gas used by the transaction.
+    #[serde(with = "super::quantity")]
+    pub gas_used: u64,
+    /// Whether the transaction has succeeded.
+    pub pass: bool,
+    /// The error the transaction has failed with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TraceSummary {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(output: &[u8], gas_used: u64, pass: bool) -> Self {
+        Self {
+            output: format!("0x{}", hex::encode(output)),
+            gas_used,
+            pass,
+            error: None,
+        }
+    }
+}
@@ -59,3 +59,26 @@ pub fn u256_to_h256(value: &web3::types::U256) -> web3::types::H256 {
 pub fn h256_to_u256(value: &web3::types::H256) -> web3::types::U256 {
     web3::types::U256::from_big_endian(value.as_bytes())
 }
+
+///
+/// Splits the data into `U256` words, padding the last one with zeros.
+///
+pub fn bytes_to_words(data: &[u8]) -> Vec<web3::types::U256> {
+    data.chunks(era_compiler_common::BYTE_LENGTH_FIELD)
+        .map(|word| {
+            let mut word_padded = word.to_vec();
+            word_padded.resize(era_compiler_common::BYTE_LENGTH_FIELD, 0);
+            web3::types::U256::from_big_endian(word_padded.as_slice())
+        })
+        .collect()
+}
+
+///
+/// Converts the test or case name into a file name, replacing everything but letters and digits.
+///
+pub fn file_name(name: &str) -> String {
+    regex::Regex::new("[^A-Za-z0-9]+")
+        .expect("Always valid")
+        .replace_all(name, "_")
+        .to_string()
+}
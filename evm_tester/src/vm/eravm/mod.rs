@@ -9,12 +9,15 @@ pub mod deployers;
 pub mod system_context;
 pub mod system_contracts;
 pub mod evm_bytecode_hash;
+pub mod step_log;
+pub mod tracing_guard;
 
 #[cfg(feature = "vm2")]
 mod vm2_adapter;
 
 use address_iterator::EraVMAddressIterator;
 use era_compiler_common::EVMVersion;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::ops::Add;
 use std::path::PathBuf;
@@ -31,23 +34,23 @@ use zksync_types::U256;
 use constants::SYSTEM_CALL_BIT;
 use zkevm_opcode_defs::ADDRESS_CONTRACT_DEPLOYER;
 
+use crate::test::case::transaction::Transaction;
+use crate::trace::trace_summary::TraceSummary;
+use crate::trace::Trace;
 use crate::utils;
+use crate::vm::evm_account::EvmAccount;
 use crate::vm::execution_result::ExecutionResult;
+use crate::vm::invalid_transaction::InvalidTransaction;
+use crate::vm::state_test_vm::StateTestVm;
 
+use self::step_log::StepLog;
 use self::system_context::SystemContext;
 use self::system_contracts::SystemContracts;
 use self::system_contracts::ADDRESS_EVM_GAS_MANAGER;
+use self::tracing_guard::TracingGuard;
 
 use super::output::ExecutionOutput;
 
-#[derive(Debug)]
-pub struct EvmAccount {
-    pub balance: U256,
-    pub nonce: U256,
-    pub code: Vec<u8>,
-    pub code_hash: H256,
-    pub storage: HashMap<U256, U256>,
-}
 ///
 /// The EraVM interface.
 ///
@@ -99,6 +102,9 @@ impl EraVM {
     /// The EVM call gas limit.
     pub const EVM_CALL_GAS_LIMIT: u64 = u32::MAX as u64;
 
+    /// Whether the state test transactions are sent with the system call ABI.
+    pub const SYSTEM_MODE: bool = true;
+
     ///
     /// Creates and initializes a new EraVM instance.
     ///
@@ -311,12 +317,7 @@ impl EraVM {
                 (zkevm_tester::compiler_tests::VmLaunchOption::Default, value)
             };
 
-        let mut trace_file_path = PathBuf::from_str("./trace/").expect("Always valid");
-        let trace_file_name = regex::Regex::new("[^A-Za-z0-9]+")
-            .expect("Always valid")
-            .replace_all(test_name.as_str(), "_")
-            .to_string();
-        trace_file_path.push(trace_file_name);
+        let trace_file_path = Self::vm_trace_path(test_name.as_str());
 
         let context = zkevm_tester::compiler_tests::VmExecutionContext::new(
             entry_address,
@@ -413,6 +414,7 @@ impl EraVM {
             return Ok(ExecutionResult {
                 output: ExecutionOutput {
                     return_data: vec![],
+                    return_data_size: 0,
                     exception: true,
                     events: vec![],
                     system_error: None,
@@ -440,6 +442,7 @@ impl EraVM {
             return Ok(ExecutionResult {
                 output: ExecutionOutput {
                     return_data: vec![],
+                    return_data_size: 0,
                     exception: true,
                     events: vec![],
                     system_error: None,
@@ -459,6 +462,7 @@ impl EraVM {
             return Ok(ExecutionResult {
                 output: ExecutionOutput {
                     return_data: vec![],
+                    return_data_size: 0,
                     exception: true,
                     events: vec![],
                     system_error: None,
@@ -579,6 +583,7 @@ impl EraVM {
                 return Ok(ExecutionResult {
                     output: ExecutionOutput {
                         return_data: vec![],
+                        return_data_size: 0,
                         exception: true,
                         events: vec![],
                         system_error: None,
@@ -639,6 +644,7 @@ impl EraVM {
             return Ok(ExecutionResult {
                 output: ExecutionOutput {
                     return_data: vec![],
+                    return_data_size: 0,
                     exception: true,
                     events: vec![],
                     system_error: None,
@@ -658,6 +664,7 @@ impl EraVM {
             return Ok(ExecutionResult {
                 output: ExecutionOutput {
                     return_data: vec![],
+                    return_data_size: 0,
                     exception: true,
                     events: vec![],
                     system_error: None,
@@ -673,6 +680,7 @@ impl EraVM {
             return Ok(ExecutionResult {
                 output: ExecutionOutput {
                     return_data: vec![],
+                    return_data_size: 0,
                     exception: true,
                     events: vec![],
                     system_error: None,
@@ -722,6 +730,10 @@ impl EraVM {
             }
         } else if result.output.system_error.is_none() {
             let gas_left = result.output.return_data.remove(0);
+            result.output.return_data_size = result
+                .output
+                .return_data_size
+                .saturating_sub(era_compiler_common::BYTE_LENGTH_FIELD);
 
             let gas_left: u64 = gas_left.try_into().unwrap();
 
@@ -735,6 +747,15 @@ impl EraVM {
         Ok(result)
     }
 
+    ///
+    /// Returns the path of the EraVM trace written for the test.
+    ///
+    fn vm_trace_path(test_name: &str) -> PathBuf {
+        let mut trace_file_path = PathBuf::from_str("./trace/").expect("Always valid");
+        trace_file_path.push(utils::file_name(test_name));
+        trace_file_path
+    }
+
     fn charge_intristic_cost_and_calldata(
         mut gas: U256,
         calldata: &Vec<u8>,
@@ -833,10 +854,8 @@ impl EraVM {
                     .cloned()
                     .expect("Always exists");
             } else {
-                code = Default::default();
-                code_hash = H256::from_slice(&keccak256(&[]));
-
-                println!("EMPTY HASH: {}", code_hash);
+                code = self.get_code(address).unwrap_or_default();
+                code_hash = H256::from_slice(&keccak256(&code));
             }
 
             let account = EvmAccount {
@@ -1156,7 +1175,7 @@ impl EraVM {
     }
 
     pub fn get_storage_slot(
-        &mut self,
+        &self,
         address: Address,
         key: web3::types::U256,
     ) -> Option<web3::types::H256> {
@@ -1299,3 +1318,147 @@ impl EraVM {
         }
     }
 }
+
+impl StateTestVm for EraVM {
+    fn clone_for_case(vm: Arc<Self>, evm_version: Option<EVMVersion>) -> Self {
+        Self::clone_with_contracts(vm, HashMap::new(), evm_version)
+    }
+
+    fn set_balance(&mut self, address: web3::types::Address, value: web3::types::U256) {
+        EraVM::set_balance(self, address, value);
+    }
+
+    fn set_nonce(&mut self, address: web3::types::Address, value: web3::types::U256) {
+        EraVM::set_nonce(self, address, value);
+    }
+
+    fn set_code(&mut self, address: web3::types::Address, bytecode: Vec<u8>) {
+        self.set_predeployed_evm_contract(address, bytecode);
+    }
+
+    fn populate_storage(
+        &mut self,
+        values: HashMap<(web3::types::Address, web3::types::U256), web3::types::H256>,
+    ) {
+        EraVM::populate_storage(self, values);
+    }
+
+    fn execute(
+        &mut self,
+        name: String,
+        transaction: &Transaction,
+        system_context: &EVMContext,
+    ) -> anyhow::Result<ExecutionResult> {
+        let caller = transaction
+            .sender
+            .ok_or_else(|| anyhow::anyhow!("The transaction sender is not specified"))?;
+        let value = u128::try_from(transaction.value).map_err(|_| {
+            InvalidTransaction::new(format!(
+                "The value {} does not fit into 128 bits",
+                transaction.value
+            ))
+        })?;
+
+        match transaction.to.0 {
+            Some(address) => self.execute_evm_interpreter::<{ Self::SYSTEM_MODE }>(
+                name,
+                address,
+                caller,
+                Some(value),
+                Some(transaction.gas_limit),
+                transaction.data.0.clone(),
+                None,
+                Some(system_context.clone()),
+            ),
+            None => self.deploy_evm::<{ Self::SYSTEM_MODE }>(
+                name,
+                caller,
+                transaction.data.0.clone(),
+                Some(value),
+                Some(transaction.gas_limit),
+                Some(system_context.clone()),
+            ),
+        }
+    }
+
+    fn execute_traced(
+        &mut self,
+        name: String,
+        transaction: &Transaction,
+        system_context: &EVMContext,
+    ) -> anyhow::Result<(ExecutionResult, Trace)> {
+        let vm_trace_path = Self::vm_trace_path(name.as_str());
+        let _ = std::fs::remove_file(vm_trace_path.as_path());
+
+        let result = {
+            let _tracing = TracingGuard::enable();
+            StateTestVm::execute(self, name.clone(), transaction, system_context)?
+        };
+
+        // The stock emulator does not report its steps, so only the summary is traced with it
+        let steps = if vm_trace_path.exists() {
+            StepLog::decode(vm_trace_path.as_path())?
+        } else {
+            vec![]
+        };
+        let mut trace = Trace::new(steps, None);
+        trace.fill_gas_costs();
+
+        let mut output = Vec::with_capacity(result.output.return_data.len() * 32);
+        for word in result.output.return_data.iter() {
+            let mut bytes = [0u8; 32];
+            word.to_big_endian(&mut bytes);
+            output.extend_from_slice(&bytes);
+        }
+        trace.summary = Some(TraceSummary::new(
+            output.as_slice(),
+            result.gas.low_u64(),
+            !result.output.exception,
+        ));
+
+        Ok((result, trace))
+    }
+
+    fn get_balance(&self, address: web3::types::Address) -> web3::types::U256 {
+        EraVM::get_balance(self, address)
+    }
+
+    fn get_nonce(&self, address: web3::types::Address) -> web3::types::U256 {
+        EraVM::get_nonce(self, address)
+    }
+
+    fn get_code(&self, address: web3::types::Address) -> Option<Vec<u8>> {
+        EraVM::get_code(self, address)
+    }
+
+    fn get_storage_slot(
+        &self,
+        address: web3::types::Address,
+        key: web3::types::U256,
+    ) -> Option<web3::types::H256> {
+        EraVM::get_storage_slot(self, address, key)
+    }
+
+    fn get_state(&self) -> HashMap<web3::types::Address, EvmAccount> {
+        EraVM::get_state(self)
+    }
+
+    fn system_contract_hashes(&self) -> BTreeMap<String, web3::types::H256> {
+        let mut hashes: BTreeMap<String, web3::types::H256> = SYSTEM_CONTRACT_LIST
+            .iter()
+            .filter_map(|(_, name, address, _)| {
+                self.get_contract_versioned_bytecode_hash(*address)
+                    .map(|hash| ((*name).to_owned(), *hash))
+            })
+            .collect();
+        hashes.insert(
+            "DefaultAccount".to_owned(),
+            utils::u256_to_h256(&self.default_aa_code_hash),
+        );
+        hashes.insert(
+            "EvmEmulator".to_owned(),
+            utils::u256_to_h256(&self.evm_interpreter_code_hash),
+        );
+        hashes
+    }
+}
@@ -0,0 +1,195 @@
+//!
+//! The EVM emulator step log decoder.
+//!
+
+use std::path::Path;
+
+use crate::trace::step::Step;
+
+///
+/// The EVM emulator step log decoder.
+///
+/// Reconstructs the EVM-level steps from the EraVM trace. The emulator built with step logging
+/// reports its interpreter state through the debug hook before every instruction: the `step`
+/// string record followed by the hexadecimal records of the program counter, opcode, gas left,
+/// memory size, stack height and the stack items starting from the top.
+///
+/// Every debug hook record is written to the heap as the record type marker, the value, and then
+/// the record magic. The call depth is derived from the heap pages of the emulator frames.
+///
+pub struct StepLog;
+
+impl StepLog {
+    /// The debug hook record magic, written after the record type marker and value.
+    const RECORD_MAGIC: &'static str =
+        "4a15830341869caa1e99840c97043a1ea15d2444da366efff5c43b4bef299681";
+
+    /// The hexadecimal value record type marker.
+    const HEX_MARKER: &'static str =
+        "debdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebde";
+
+    /// The string value record type marker.
+    const STRING_MARKER: &'static str =
+        "debdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebdebdf";
+
+    /// The string record starting a step.
+    const STEP_RECORD: &'static str = "step";
+
+    /// The number of hexadecimal records preceding the stack items.
+    const STEP_HEADER_SIZE: usize = 5;
+
+    ///
+    /// Decodes the EVM steps from the EraVM trace file.
+    ///
+    pub fn decode(vm_trace_path: &Path) -> anyhow::Result<Vec<Step>> {
+        let vm_trace = std::fs::read_to_string(vm_trace_path).map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to read the EraVM trace `{}`: {error}",
+                vm_trace_path.to_string_lossy()
+            )
+        })?;
+        let vm_trace: serde_json::Value = serde_json::from_str(vm_trace.as_str())?;
+
+        let records = Self::records(&vm_trace);
+        Ok(Self::steps(records))
+    }
+
+    ///
+    /// Extracts the debug hook records from the heap writes of the EraVM trace.
+    ///
+    /// Returns the heap page, whether the record is a string, and the value of every record.
+    ///
+    fn records(vm_trace: &serde_json::Value) -> Vec<(u64, bool, web3::types::U256)> {
+        let mut records = Vec::new();
+        let mut marker = None;
+        let mut value = None;
+
+        let interactions = vm_trace
+            .get("steps")
+            .and_then(|steps| steps.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|step| step.get("memory_interactions"))
+            .filter_map(|interactions| interactions.as_array())
+            .flatten();
+        for interaction in interactions {
+            let is_heap_write = interaction
+                .get("memory_type")
+                .and_then(|memory_type| memory_type.as_str())
+                .map(|memory_type| memory_type.eq_ignore_ascii_case("heap"))
+                .unwrap_or_default()
+                && interaction
+                    .get("direction")
+                    .and_then(|direction| direction.as_str())
+                    .map(|direction| direction.eq_ignore_ascii_case("write"))
+                    .unwrap_or_default();
+            if !is_heap_write {
+                continue;
+            }
+
+            let page = interaction
+                .get("page")
+                .and_then(|page| page.as_u64())
+                .unwrap_or_default();
+            let word = match interaction.get("value").and_then(Self::parse_word) {
+                Some(word) => word,
+                None => continue,
+            };
+            let hex = format!("{word:064x}");
+
+            if hex == Self::RECORD_MAGIC {
+                if let (Some(is_string), Some(value)) = (marker.take(), value.take()) {
+                    records.push((page, is_string, value));
+                }
+            } else if hex.ends_with(Self::HEX_MARKER) {
+                marker = Some(false);
+                value = None;
+            } else if hex.ends_with(Self::STRING_MARKER) {
+                marker = Some(true);
+                value = None;
+            } else if marker.is_some() {
+                value = Some(word);
+            }
+        }
+
+        records
+    }
+
+    ///
+    /// Assembles the steps from the debug hook records.
+    ///
+    /// Incomplete steps, which are interrupted by another record, are dropped.
+    ///
+    fn steps(records: Vec<(u64, bool, web3::types::U256)>) -> Vec<Step> {
+        let mut steps = Vec::new();
+        let mut pages: Vec<u64> = Vec::new();
+        let mut fields: Option<Vec<web3::types::U256>> = None;
+
+        for (page, is_string, value) in records.into_iter() {
+            if is_string {
+                fields = if Self::parse_string(value) == Self::STEP_RECORD {
+                    match pages.iter().position(|frame_page| *frame_page == page) {
+                        Some(position) => pages.truncate(position + 1),
+                        None => pages.push(page),
+                    }
+                    Some(Vec::with_capacity(Self::STEP_HEADER_SIZE))
+                } else {
+                    None
+                };
+                continue;
+            }
+
+            let complete = match fields.as_mut() {
+                Some(fields) => {
+                    fields.push(value);
+                    fields.len() >= Self::STEP_HEADER_SIZE
+                        && fields.len() == Self::STEP_HEADER_SIZE + fields[4].low_u64() as usize
+                }
+                None => false,
+            };
+            if complete {
+                let fields = fields.take().expect("Always exists");
+                let stack = fields[Self::STEP_HEADER_SIZE..]
+                    .iter()
+                    .rev()
+                    .copied()
+                    .collect();
+                steps.push(Step::new(
+                    fields[0].low_u64(),
+                    fields[1].low_u32() as u8,
+                    fields[2].low_u64(),
+                    fields[3].low_u64(),
+                    stack,
+                    pages.len() as u64,
+                ));
+            }
+        }
+
+        steps
+    }
+
+    ///
+    /// Parses a memory word, which is written either as a hexadecimal string or as a number.
+    ///
+    fn parse_word(value: &serde_json::Value) -> Option<web3::types::U256> {
+        match value {
+            serde_json::Value::String(value) => match value.strip_prefix("0x") {
+                Some(hex) => web3::types::U256::from_str_radix(hex, 16).ok(),
+                None => web3::types::U256::from_dec_str(value).ok(),
+            },
+            serde_json::Value::Number(value) => value.as_u64().map(web3::types::U256::from),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Parses a left-aligned string record.
+    ///
+    fn parse_string(value: web3::types::U256) -> String {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+        String::from_utf8_lossy(&bytes)
+            .trim_end_matches('\0')
+            .to_owned()
+    }
+}
@@ -13,7 +13,8 @@ use super::utils;
 ///
 pub struct SystemContext;
 
-#[derive(Clone)]
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EVMContext {
     pub chain_id: u64,
     pub coinbase: web3::types::Address,
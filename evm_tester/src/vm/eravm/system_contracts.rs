@@ -204,14 +204,14 @@ impl SystemContracts {
     ///
     pub fn build() -> anyhow::Result<Self> {
         let build_time_start = Instant::now();
-        println!("    {} system contracts", "Building".bright_green().bold());
+        eprintln!("    {} system contracts", "Building".bright_green().bold());
 
         let system_contracts_path = PathBuf::from("era-contracts/system-contracts");
 
         let system_contracts =
             get_system_smart_contracts_from_dir(system_contracts_path.clone(), true);
 
-        println!(
+        eprintln!(
             "    {} building system contracts in {}.{:03}s",
             "Finished".bright_green().bold(),
             build_time_start.elapsed().as_secs(),
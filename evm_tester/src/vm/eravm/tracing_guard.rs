@@ -0,0 +1,44 @@
+//!
+//! The EraVM tracing mode guard.
+//!
+
+use std::sync::Mutex;
+
+/// The number of traced executions in progress across all threads.
+static ACTIVE_TRACED_EXECUTIONS: Mutex<usize> = Mutex::new(0);
+
+///
+/// The EraVM tracing mode guard.
+///
+/// The tracing mode is global for the process, so it is enabled by the first traced execution
+/// and disabled again once the last one running in parallel has finished.
+///
+pub struct TracingGuard;
+
+impl TracingGuard {
+    ///
+    /// Enables the verbose EraVM tracing until the guard is dropped.
+    ///
+    pub fn enable() -> Self {
+        let mut active = ACTIVE_TRACED_EXECUTIONS.lock().expect("Sync");
+        if *active == 0 {
+            zkevm_tester::compiler_tests::set_tracing_mode(
+                zkevm_tester::compiler_tests::VmTracingOptions::ManualVerbose,
+            );
+        }
+        *active += 1;
+        Self
+    }
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        let mut active = ACTIVE_TRACED_EXECUTIONS.lock().expect("Sync");
+        *active -= 1;
+        if *active == 0 {
+            zkevm_tester::compiler_tests::set_tracing_mode(
+                zkevm_tester::compiler_tests::VmTracingOptions::None,
+            );
+        }
+    }
+}
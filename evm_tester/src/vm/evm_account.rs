@@ -0,0 +1,33 @@
+//!
+//! The EVM account state.
+//!
+
+use std::collections::HashMap;
+
+///
+/// The EVM account state.
+///
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct EvmAccount {
+    /// The account balance.
+    pub balance: web3::types::U256,
+    /// The account nonce.
+    pub nonce: web3::types::U256,
+    /// The account code.
+    #[serde(serialize_with = "serialize_code")]
+    pub code: Vec<u8>,
+    /// The account code `keccak256` hash.
+    pub code_hash: web3::types::H256,
+    /// The account storage.
+    pub storage: HashMap<web3::types::U256, web3::types::U256>,
+}
+
+///
+/// Serializes the account code as a `0x`-prefixed hexadecimal string.
+///
+fn serialize_code<S>(code: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(format!("0x{}", hex::encode(code)).as_str())
+}
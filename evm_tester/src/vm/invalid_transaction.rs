@@ -0,0 +1,32 @@
+//!
+//! The invalid transaction error.
+//!
+
+///
+/// The invalid transaction error.
+///
+/// Is returned by the VMs which reject the transaction before executing it, e.g. if its gas
+/// limit is below the intrinsic gas, so the rejection is not mistaken for an exception.
+///
+#[derive(Debug)]
+pub struct InvalidTransaction {
+    /// The rejection reason.
+    pub reason: String,
+}
+
+impl InvalidTransaction {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(reason: String) -> Self {
+        Self { reason }
+    }
+}
+
+impl std::fmt::Display for InvalidTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid transaction: {}", self.reason)
+    }
+}
+
+impl std::error::Error for InvalidTransaction {}
@@ -4,5 +4,13 @@
 
 pub mod address_iterator;
 pub mod eravm;
+pub mod evm_account;
 pub mod execution_result;
+pub mod invalid_transaction;
+pub mod opcode;
 pub mod output;
+pub mod precompile;
+pub mod revm;
+pub mod sputnikvm;
+pub mod state_root;
+pub mod state_test_vm;
@@ -11,6 +11,8 @@ pub mod value;
 pub struct ExecutionOutput {
     /// The return data values.
     pub return_data: Vec<Value>,
+    /// The return data size in bytes, as the last value is zero-padded.
+    pub return_data_size: usize,
     /// Whether an exception is thrown,
     pub exception: bool,
     /// The emitted events.
@@ -29,18 +31,28 @@ impl ExecutionOutput {
         system_error: Option<(usize, usize)>,
     ) -> Self {
         Self {
+            return_data_size: return_data.len() * era_compiler_common::BYTE_LENGTH_FIELD,
             return_data,
             exception,
             events,
             system_error,
         }
     }
+
+    ///
+    /// Sets the exact return data size, if the last value is padded.
+    ///
+    pub fn with_return_data_size(mut self, return_data_size: usize) -> Self {
+        self.return_data_size = return_data_size;
+        self
+    }
 }
 
 impl From<web3::types::U256> for ExecutionOutput {
     fn from(value: web3::types::U256) -> Self {
         Self {
             return_data: vec![value],
+            return_data_size: era_compiler_common::BYTE_LENGTH_FIELD,
             exception: false,
             events: vec![],
             system_error: None,
@@ -77,6 +89,7 @@ impl From<zkevm_tester::compiler_tests::VmSnapshot> for ExecutionOutput {
 
         match snapshot.execution_result {
             zkevm_tester::compiler_tests::VmExecutionResult::Ok(return_data) => {
+                let return_data_size = return_data.len();
                 let return_data = return_data
                     .chunks(era_compiler_common::BYTE_LENGTH_FIELD)
                     .map(|word| {
@@ -97,12 +110,14 @@ impl From<zkevm_tester::compiler_tests::VmSnapshot> for ExecutionOutput {
 
                 Self {
                     return_data,
+                    return_data_size,
                     exception: false,
                     events,
                     system_error: None,
                 }
             }
             zkevm_tester::compiler_tests::VmExecutionResult::Revert(return_data) => {
+                let return_data_size = return_data.len();
                 let return_data: Vec<_> = return_data
                     .chunks(era_compiler_common::BYTE_LENGTH_FIELD)
                     .map(|word| {
@@ -144,6 +159,7 @@ impl From<zkevm_tester::compiler_tests::VmSnapshot> for ExecutionOutput {
 
                 Self {
                     return_data,
+                    return_data_size,
                     exception: true,
                     events,
                     system_error,
@@ -151,6 +167,7 @@ impl From<zkevm_tester::compiler_tests::VmSnapshot> for ExecutionOutput {
             }
             zkevm_tester::compiler_tests::VmExecutionResult::Panic => Self {
                 return_data: vec![],
+                return_data_size: 0,
                 exception: true,
                 events,
                 system_error: None,
@@ -158,6 +175,7 @@ impl From<zkevm_tester::compiler_tests::VmSnapshot> for ExecutionOutput {
             zkevm_tester::compiler_tests::VmExecutionResult::MostLikelyDidNotFinish { .. } => {
                 Self {
                     return_data: vec![],
+                    return_data_size: 0,
                     exception: true,
                     events,
                     system_error: None,
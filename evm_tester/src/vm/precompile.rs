@@ -0,0 +1,30 @@
+//!
+//! The EVM precompiles.
+//!
+
+///
+/// Returns the name of the precompile at the address, `UNKNOWN` for the unassigned ones.
+///
+pub fn name(address: usize) -> &'static str {
+    match address {
+        0x01 => "ECRECOVER",
+        0x02 => "SHA256",
+        0x03 => "RIPEMD160",
+        0x04 => "IDENTITY",
+        0x05 => "MODEXP",
+        0x06 => "ECADD",
+        0x07 => "ECMUL",
+        0x08 => "ECPAIRING",
+        0x09 => "BLAKE2F",
+        0x0a => "POINT_EVALUATION",
+        0x0b => "BLS12_G1ADD",
+        0x0c => "BLS12_G1MSM",
+        0x0d => "BLS12_G2ADD",
+        0x0e => "BLS12_G2MSM",
+        0x0f => "BLS12_PAIRING_CHECK",
+        0x10 => "BLS12_MAP_FP_TO_G1",
+        0x11 => "BLS12_MAP_FP2_TO_G2",
+        0x100 => "P256VERIFY",
+        _ => "UNKNOWN",
+    }
+}
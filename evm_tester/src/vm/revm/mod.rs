@@ -0,0 +1,460 @@
+//!
+//! The revm interface.
+//!
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use revm::db::AccountState;
+use revm::db::CacheDB;
+use revm::db::DbAccount;
+use revm::db::EmptyDB;
+use revm::inspector_handle_register;
+use revm::inspectors::TracerEip3155;
+use revm::primitives::AccessListItem;
+use revm::primitives::AccountInfo;
+use revm::primitives::Bytecode;
+use revm::primitives::Bytes;
+use revm::primitives::EVMError;
+use revm::primitives::Env;
+use revm::primitives::ExecutionResult as RevmExecutionResult;
+use revm::primitives::SpecId;
+use revm::primitives::TxKind;
+use revm::primitives::B256;
+use revm::primitives::KECCAK_EMPTY;
+
+use crate::test::case::transaction::Transaction;
+use crate::trace::trace_summary::TraceSummary;
+use crate::trace::Trace;
+use crate::utils;
+use crate::vm::eravm::system_context::EVMContext;
+use crate::vm::evm_account::EvmAccount;
+use crate::vm::execution_result::ExecutionResult;
+use crate::vm::invalid_transaction::InvalidTransaction;
+use crate::vm::output::event::Event;
+use crate::vm::output::value::Value;
+use crate::vm::output::ExecutionOutput;
+use crate::vm::state_test_vm::StateTestVm;
+
+///
+/// The revm interface.
+///
+/// Is used as the reference EVM implementation.
+///
+#[derive(Clone, Default)]
+pub struct Revm {
+    /// The in-memory state database.
+    db: CacheDB<EmptyDB>,
+    /// The prestate value revm cannot represent, which makes the case invalid.
+    prestate_error: Option<String>,
+}
+
+impl Revm {
+    /// The hard fork the reference EVM is configured for.
+    pub const SPEC_ID: SpecId = SpecId::CANCUN;
+
+    ///
+    /// Creates a new revm instance with an empty state.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Returns the existing account at the specified address.
+    ///
+    fn account(&self, address: web3::types::Address) -> Option<&DbAccount> {
+        self.db
+            .accounts
+            .get(&to_revm_address(&address))
+            .filter(|account| !matches!(account.account_state, AccountState::NotExisting))
+    }
+
+    ///
+    /// Returns the account at the specified address, creating it if it does not exist.
+    ///
+    fn account_mut(&mut self, address: web3::types::Address) -> &mut DbAccount {
+        let account = self
+            .db
+            .accounts
+            .entry(to_revm_address(&address))
+            .or_default();
+        if matches!(account.account_state, AccountState::NotExisting) {
+            account.account_state = AccountState::None;
+        }
+        account
+    }
+
+    ///
+    /// Fails if the prestate could not be populated.
+    ///
+    fn check_prestate(&self) -> anyhow::Result<()> {
+        match self.prestate_error.as_ref() {
+            Some(error) => anyhow::bail!("Invalid prestate: {error}"),
+            None => Ok(()),
+        }
+    }
+
+    ///
+    /// Builds the revm environment of the state test transaction.
+    ///
+    /// The transactions with the fields revm cannot represent are rejected as invalid.
+    ///
+    fn environment(
+        transaction: &Transaction,
+        system_context: &EVMContext,
+    ) -> anyhow::Result<Box<Env>> {
+        let mut env = Box::<Env>::default();
+
+        env.cfg.chain_id = system_context.chain_id;
+
+        env.block.number = revm::primitives::U256::from(system_context.block_number);
+        env.block.timestamp = revm::primitives::U256::from(system_context.block_timestamp);
+        env.block.coinbase = to_revm_address(&system_context.coinbase);
+        env.block.gas_limit = to_revm_u256(&system_context.block_gas_limit);
+        env.block.basefee = to_revm_u256(&system_context.base_fee);
+        env.block.difficulty =
+            revm::primitives::U256::from_be_slice(system_context.block_difficulty.as_bytes());
+        env.block.prevrandao = Some(B256::from_slice(system_context.block_difficulty.as_bytes()));
+
+        env.tx.caller = to_revm_address(&transaction.sender.unwrap_or_default());
+        env.tx.gas_limit = u64::try_from(transaction.gas_limit).map_err(|_| {
+            InvalidTransaction::new(format!(
+                "the gas limit {} exceeds 64 bits",
+                transaction.gas_limit
+            ))
+        })?;
+        match transaction.max_fee_per_gas {
+            Some(ref max_fee_per_gas) => {
+                env.tx.gas_price = to_revm_u256(max_fee_per_gas);
+                env.tx.gas_priority_fee = Some(to_revm_u256(
+                    &transaction.max_priority_fee_per_gas.unwrap_or_default(),
+                ));
+            }
+            None => env.tx.gas_price = to_revm_u256(&system_context.gas_price),
+        }
+        env.tx.transact_to = match transaction.to.0 {
+            Some(address) => TxKind::Call(to_revm_address(&address)),
+            None => TxKind::Create,
+        };
+        env.tx.value = to_revm_u256(&transaction.value);
+        env.tx.data = Bytes::from(transaction.data.0.clone());
+        env.tx.nonce = Some(u64::try_from(transaction.nonce).map_err(|_| {
+            InvalidTransaction::new(format!("the nonce {} exceeds 64 bits", transaction.nonce))
+        })?);
+        env.tx.chain_id = Some(system_context.chain_id);
+        env.tx.access_list = transaction
+            .access_list
+            .iter()
+            .flatten()
+            .map(|item| AccessListItem {
+                address: to_revm_address(&item.address),
+                storage_keys: item
+                    .storage_keys
+                    .iter()
+                    .map(|key| B256::from_slice(key.as_bytes()))
+                    .collect(),
+            })
+            .collect();
+
+        Ok(env)
+    }
+
+    ///
+    /// Converts the revm transaction result.
+    ///
+    fn convert_result<E>(
+        result: Result<RevmExecutionResult, EVMError<E>>,
+    ) -> anyhow::Result<ExecutionResult>
+    where
+        E: std::fmt::Debug,
+    {
+        let result = match result {
+            Ok(result) => result,
+            Err(EVMError::Transaction(error)) => {
+                return Err(InvalidTransaction::new(format!("{error:?}")).into())
+            }
+            Err(error) => anyhow::bail!("revm failure: {error:?}"),
+        };
+
+        let gas_used = web3::types::U256::from(result.gas_used());
+        let output = match result {
+            RevmExecutionResult::Success { output, logs, .. } => {
+                let events = logs
+                    .into_iter()
+                    .map(|log| {
+                        Event::new(
+                            Some(web3::types::Address::from_slice(log.address.as_slice())),
+                            log.data
+                                .topics()
+                                .iter()
+                                .map(|topic| Value::from_big_endian(topic.as_slice()))
+                                .collect(),
+                            utils::bytes_to_words(log.data.data.as_ref()),
+                        )
+                    })
+                    .collect();
+                ExecutionOutput::new(
+                    utils::bytes_to_words(output.data().as_ref()),
+                    false,
+                    events,
+                    None,
+                )
+                .with_return_data_size(output.data().len())
+            }
+            RevmExecutionResult::Revert { output, .. } => {
+                ExecutionOutput::new(utils::bytes_to_words(output.as_ref()), true, vec![], None)
+                    .with_return_data_size(output.len())
+            }
+            RevmExecutionResult::Halt { .. } => ExecutionOutput::new(vec![], true, vec![], None),
+        };
+
+        Ok(ExecutionResult::new(output, 0, 0, gas_used))
+    }
+}
+
+///
+/// The shared buffer the revm tracer writes the EIP-3155 lines to.
+///
+#[derive(Clone, Default)]
+struct TraceBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for TraceBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().expect("Sync").extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl StateTestVm for Revm {
+    ///
+    /// Executes a state test transaction, committing the resulting state.
+    ///
+    /// Transactions rejected by the validation are reported as errors without any state change.
+    ///
+    fn execute(
+        &mut self,
+        _name: String,
+        transaction: &Transaction,
+        system_context: &EVMContext,
+    ) -> anyhow::Result<ExecutionResult> {
+        self.check_prestate()?;
+        let environment = Self::environment(transaction, system_context)?;
+
+        let result = revm::Evm::builder()
+            .with_db(&mut self.db)
+            .with_env(environment)
+            .with_spec_id(Self::SPEC_ID)
+            .build()
+            .transact_commit();
+
+        Self::convert_result(result)
+    }
+
+    ///
+    /// Executes a state test transaction with the revm EIP-3155 tracer.
+    ///
+    fn execute_traced(
+        &mut self,
+        _name: String,
+        transaction: &Transaction,
+        system_context: &EVMContext,
+    ) -> anyhow::Result<(ExecutionResult, Trace)> {
+        self.check_prestate()?;
+        let environment = Self::environment(transaction, system_context)?;
+
+        let buffer = TraceBuffer::default();
+        let result = revm::Evm::builder()
+            .with_db(&mut self.db)
+            .with_env(environment)
+            .with_external_context(TracerEip3155::new(Box::new(buffer.clone())))
+            .with_spec_id(Self::SPEC_ID)
+            .append_handler_register(inspector_handle_register)
+            .build()
+            .transact_commit();
+        let result = Self::convert_result(result)?;
+
+        let trace = buffer.0.lock().expect("Sync");
+        let mut trace = Trace::from_json_lines(String::from_utf8_lossy(trace.as_slice()).as_ref())?;
+        let mut output = Vec::with_capacity(result.output.return_data.len() * 32);
+        for word in result.output.return_data.iter() {
+            let mut bytes = [0u8; 32];
+            word.to_big_endian(&mut bytes);
+            output.extend_from_slice(&bytes);
+        }
+        trace.summary = Some(TraceSummary::new(
+            output.as_slice(),
+            result.gas.low_u64(),
+            !result.output.exception,
+        ));
+
+        Ok((result, trace))
+    }
+
+    ///
+    /// Returns the balance of the specified address.
+    ///
+    fn get_balance(&self, address: web3::types::Address) -> web3::types::U256 {
+        self.account(address)
+            .map(|account| from_revm_u256(&account.info.balance))
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Changes the balance of the specified address.
+    ///
+    fn set_balance(&mut self, address: web3::types::Address, value: web3::types::U256) {
+        self.account_mut(address).info.balance = to_revm_u256(&value);
+    }
+
+    ///
+    /// Returns the nonce of the specified address.
+    ///
+    fn get_nonce(&self, address: web3::types::Address) -> web3::types::U256 {
+        self.account(address)
+            .map(|account| web3::types::U256::from(account.info.nonce))
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Changes the nonce of the specified address.
+    ///
+    fn set_nonce(&mut self, address: web3::types::Address, value: web3::types::U256) {
+        match u64::try_from(value) {
+            Ok(nonce) => self.account_mut(address).info.nonce = nonce,
+            Err(_) => {
+                self.prestate_error =
+                    Some(format!("the nonce {value} of {address:?} exceeds 64 bits"))
+            }
+        }
+    }
+
+    ///
+    /// Returns the code of the specified address, `None` if there is no code.
+    ///
+    fn get_code(&self, address: web3::types::Address) -> Option<Vec<u8>> {
+        let account = self.account(address)?;
+        if account.info.code_hash == KECCAK_EMPTY {
+            return None;
+        }
+
+        account
+            .info
+            .code
+            .as_ref()
+            .or_else(|| self.db.contracts.get(&account.info.code_hash))
+            .map(|code| code.original_bytes().to_vec())
+    }
+
+    ///
+    /// Sets the code of the specified address.
+    ///
+    fn set_code(&mut self, address: web3::types::Address, bytecode: Vec<u8>) {
+        let address = to_revm_address(&address);
+        let info = self
+            .db
+            .accounts
+            .get(&address)
+            .map(|account| account.info.clone())
+            .unwrap_or_default();
+
+        let bytecode = Bytecode::new_raw(Bytes::from(bytecode));
+        let info = if bytecode.is_empty() {
+            AccountInfo {
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                ..info
+            }
+        } else {
+            AccountInfo {
+                code_hash: bytecode.hash_slow(),
+                code: Some(bytecode),
+                ..info
+            }
+        };
+        self.db.insert_account_info(address, info);
+    }
+
+    ///
+    /// Returns the storage slot value of the specified address, `None` if the slot is not set.
+    ///
+    fn get_storage_slot(
+        &self,
+        address: web3::types::Address,
+        key: web3::types::U256,
+    ) -> Option<web3::types::H256> {
+        self.account(address)?
+            .storage
+            .get(&to_revm_u256(&key))
+            .map(|value| web3::types::H256::from(value.to_be_bytes::<32>()))
+    }
+
+    ///
+    /// Adds values to storage.
+    ///
+    fn populate_storage(
+        &mut self,
+        values: HashMap<(web3::types::Address, web3::types::U256), web3::types::H256>,
+    ) {
+        for ((address, key), value) in values.into_iter() {
+            self.account_mut(address).storage.insert(
+                to_revm_u256(&key),
+                revm::primitives::U256::from_be_slice(value.as_bytes()),
+            );
+        }
+    }
+
+    ///
+    /// Returns the state of all existing accounts.
+    ///
+    fn get_state(&self) -> HashMap<web3::types::Address, EvmAccount> {
+        self.db
+            .accounts
+            .iter()
+            .filter(|(_, account)| !matches!(account.account_state, AccountState::NotExisting))
+            .map(|(address, account)| {
+                let address = web3::types::Address::from_slice(address.as_slice());
+                let account = EvmAccount {
+                    balance: from_revm_u256(&account.info.balance),
+                    nonce: web3::types::U256::from(account.info.nonce),
+                    code: self.get_code(address).unwrap_or_default(),
+                    code_hash: web3::types::H256::from(account.info.code_hash.0),
+                    storage: account
+                        .storage
+                        .iter()
+                        .map(|(key, value)| (from_revm_u256(key), from_revm_u256(value)))
+                        .collect(),
+                };
+                (address, account)
+            })
+            .collect()
+    }
+}
+
+///
+/// Converts `Address` into the revm address.
+///
+fn to_revm_address(address: &web3::types::Address) -> revm::primitives::Address {
+    revm::primitives::Address::from_slice(address.as_bytes())
+}
+
+///
+/// Converts `U256` into the revm `U256`.
+///
+fn to_revm_u256(value: &web3::types::U256) -> revm::primitives::U256 {
+    let mut bytes = [0u8; era_compiler_common::BYTE_LENGTH_FIELD];
+    value.to_big_endian(&mut bytes);
+    revm::primitives::U256::from_be_bytes(bytes)
+}
+
+///
+/// Converts the revm `U256` into `U256`.
+///
+fn from_revm_u256(value: &revm::primitives::U256) -> web3::types::U256 {
+    web3::types::U256::from_big_endian(&value.to_be_bytes::<32>())
+}
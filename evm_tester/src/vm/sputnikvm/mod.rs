@@ -0,0 +1,280 @@
+//!
+//! The SputnikVM interface.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use evm::backend::InMemoryAccount;
+use evm::backend::InMemoryBackend;
+use evm::backend::InMemoryEnvironment;
+use evm::backend::OverlayedBackend;
+use evm::standard::Config;
+use evm::standard::Etable;
+use evm::standard::EtableResolver;
+use evm::standard::Invoker;
+use evm::standard::TransactArgs;
+use evm::standard::TransactValueCallCreate;
+use evm_precompile::StandardPrecompileSet;
+
+use crate::test::case::transaction::Transaction;
+use crate::utils;
+use crate::vm::eravm::system_context::EVMContext;
+use crate::vm::evm_account::EvmAccount;
+use crate::vm::execution_result::ExecutionResult;
+use crate::vm::invalid_transaction::InvalidTransaction;
+use crate::vm::output::event::Event;
+use crate::vm::output::ExecutionOutput;
+use crate::vm::state_test_vm::StateTestVm;
+
+///
+/// The SputnikVM interface.
+///
+/// Is used as the second reference EVM implementation, with the standard precompile set.
+///
+#[derive(Clone, Default)]
+pub struct SputnikVM {
+    /// The account states.
+    state: BTreeMap<web3::types::Address, InMemoryAccount>,
+}
+
+impl SputnikVM {
+    /// The maximum call stack depth executed without spawning a new heap frame.
+    const HEAP_DEPTH: usize = 4;
+
+    ///
+    /// Creates a new SputnikVM instance with an empty state.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateTestVm for SputnikVM {
+    ///
+    /// Executes a state test transaction, committing the resulting state.
+    ///
+    /// The transactions rejected before execution, which leave the sender nonce intact, are
+    /// reported as invalid. The VM only reports the gas used by the successful transactions, so
+    /// for the failed ones it is derived from the fee, as the rest of their changes is reverted.
+    /// With a zero gas price, the whole gas limit is reported for them.
+    ///
+    fn execute(
+        &mut self,
+        _name: String,
+        transaction: &Transaction,
+        system_context: &EVMContext,
+    ) -> anyhow::Result<ExecutionResult> {
+        let config = Config::cancun();
+
+        let caller = transaction.sender.unwrap_or_default();
+        let access_list: Vec<(web3::types::Address, Vec<web3::types::H256>)> = transaction
+            .access_list
+            .iter()
+            .flatten()
+            .map(|item| (item.address, item.storage_keys.clone()))
+            .collect();
+        let args = match transaction.to.0 {
+            Some(address) => TransactArgs::Call {
+                caller,
+                address,
+                value: transaction.value,
+                data: transaction.data.0.clone(),
+                gas_limit: transaction.gas_limit,
+                gas_price: system_context.gas_price,
+                access_list,
+            },
+            None => TransactArgs::Create {
+                caller,
+                value: transaction.value,
+                init_code: transaction.data.0.clone(),
+                salt: None,
+                gas_limit: transaction.gas_limit,
+                gas_price: system_context.gas_price,
+                access_list,
+            },
+        };
+
+        let environment = InMemoryEnvironment {
+            block_hashes: BTreeMap::new(),
+            block_number: web3::types::U256::from(system_context.block_number),
+            block_coinbase: system_context.coinbase,
+            block_timestamp: web3::types::U256::from(system_context.block_timestamp),
+            block_difficulty: utils::h256_to_u256(&system_context.block_difficulty),
+            block_randomness: Some(system_context.block_difficulty),
+            block_gas_limit: system_context.block_gas_limit,
+            block_base_fee_per_gas: system_context.base_fee,
+            chain_id: web3::types::U256::from(system_context.chain_id),
+        };
+        let base_backend = InMemoryBackend {
+            environment,
+            state: self.state.clone(),
+        };
+
+        let gas_etable = Etable::single(evm::standard::eval_gasometer);
+        let exec_etable = Etable::runtime();
+        let etable = (gas_etable, exec_etable);
+        let precompiles = StandardPrecompileSet::new(&config);
+        let resolver = EtableResolver::new(&config, &precompiles, &etable);
+        let invoker = Invoker::new(&config, &resolver);
+
+        let mut run_backend = OverlayedBackend::new(&base_backend, BTreeSet::new());
+        let result = evm::transact(args, Some(Self::HEAP_DEPTH), &mut run_backend, &invoker);
+        let changeset = run_backend.deconstruct().1;
+
+        let mut final_backend = base_backend.clone();
+        final_backend.apply_overlayed(&changeset);
+        self.state = final_backend.state;
+
+        let account_before = base_backend.state.get(&caller).cloned().unwrap_or_default();
+
+        let (output, gas_used) = match result {
+            Ok(value) => {
+                let (return_data, return_data_size) = match value.call_create {
+                    TransactValueCallCreate::Call { retval, .. } => {
+                        (utils::bytes_to_words(retval.as_slice()), retval.len())
+                    }
+                    TransactValueCallCreate::Create { .. } => (vec![], 0),
+                };
+                let events = changeset
+                    .logs
+                    .iter()
+                    .map(|log| {
+                        Event::new(
+                            Some(log.address),
+                            log.topics.iter().map(utils::h256_to_u256).collect(),
+                            utils::bytes_to_words(log.data.as_slice()),
+                        )
+                    })
+                    .collect();
+                let output = ExecutionOutput::new(return_data, false, events, None)
+                    .with_return_data_size(return_data_size);
+                (output, value.used_gas)
+            }
+            Err(error) if self.get_nonce(caller) == account_before.nonce => {
+                return Err(InvalidTransaction::new(format!("{error:?}")).into());
+            }
+            Err(_) => {
+                let fee = account_before
+                    .balance
+                    .saturating_sub(self.get_balance(caller));
+                let gas_used = if system_context.gas_price.is_zero() {
+                    transaction.gas_limit
+                } else {
+                    fee / system_context.gas_price
+                };
+                (ExecutionOutput::new(vec![], true, vec![], None), gas_used)
+            }
+        };
+
+        Ok(ExecutionResult::new(output, 0, 0, gas_used))
+    }
+
+    ///
+    /// Returns the balance of the specified address.
+    ///
+    fn get_balance(&self, address: web3::types::Address) -> web3::types::U256 {
+        self.state
+            .get(&address)
+            .map(|account| account.balance)
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Changes the balance of the specified address.
+    ///
+    fn set_balance(&mut self, address: web3::types::Address, value: web3::types::U256) {
+        self.state.entry(address).or_default().balance = value;
+    }
+
+    ///
+    /// Returns the nonce of the specified address.
+    ///
+    fn get_nonce(&self, address: web3::types::Address) -> web3::types::U256 {
+        self.state
+            .get(&address)
+            .map(|account| account.nonce)
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Changes the nonce of the specified address.
+    ///
+    fn set_nonce(&mut self, address: web3::types::Address, value: web3::types::U256) {
+        self.state.entry(address).or_default().nonce = value;
+    }
+
+    ///
+    /// Returns the code of the specified address, `None` if there is no code.
+    ///
+    fn get_code(&self, address: web3::types::Address) -> Option<Vec<u8>> {
+        self.state
+            .get(&address)
+            .filter(|account| !account.code.is_empty())
+            .map(|account| account.code.clone())
+    }
+
+    ///
+    /// Sets the code of the specified address.
+    ///
+    fn set_code(&mut self, address: web3::types::Address, bytecode: Vec<u8>) {
+        self.state.entry(address).or_default().code = bytecode;
+    }
+
+    ///
+    /// Returns the storage slot value of the specified address, `None` if the slot is not set.
+    ///
+    fn get_storage_slot(
+        &self,
+        address: web3::types::Address,
+        key: web3::types::U256,
+    ) -> Option<web3::types::H256> {
+        self.state
+            .get(&address)?
+            .storage
+            .get(&utils::u256_to_h256(&key))
+            .copied()
+    }
+
+    ///
+    /// Adds values to storage.
+    ///
+    fn populate_storage(
+        &mut self,
+        values: HashMap<(web3::types::Address, web3::types::U256), web3::types::H256>,
+    ) {
+        for ((address, key), value) in values.into_iter() {
+            self.state
+                .entry(address)
+                .or_default()
+                .storage
+                .insert(utils::u256_to_h256(&key), value);
+        }
+    }
+
+    ///
+    /// Returns the state of all existing accounts.
+    ///
+    fn get_state(&self) -> HashMap<web3::types::Address, EvmAccount> {
+        self.state
+            .iter()
+            .map(|(address, account)| {
+                let account = EvmAccount {
+                    balance: account.balance,
+                    nonce: account.nonce,
+                    code: account.code.clone(),
+                    code_hash: web3::types::H256::from(web3::signing::keccak256(
+                        account.code.as_slice(),
+                    )),
+                    storage: account
+                        .storage
+                        .iter()
+                        .map(|(key, value)| (utils::h256_to_u256(key), utils::h256_to_u256(value)))
+                        .collect(),
+                };
+                (*address, account)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,222 @@
+//!
+//! The EVM state root.
+//!
+
+use std::collections::HashMap;
+
+use crate::vm::evm_account::EvmAccount;
+
+///
+/// The EVM state root.
+///
+/// The root of the Merkle Patricia trie of the accounts keyed by the `keccak256` hash of their
+/// addresses, with the storage tries keyed by the hash of their slots, as the state tests report it.
+/// The empty accounts and the zero storage slots are left out, as they are removed from the state.
+///
+pub struct StateRoot;
+
+impl StateRoot {
+    ///
+    /// Computes the state root of the specified accounts.
+    ///
+    pub fn compute(state: &HashMap<web3::types::Address, EvmAccount>) -> web3::types::H256 {
+        let accounts = state
+            .iter()
+            .filter(|(_, account)| {
+                !account.nonce.is_zero() || !account.balance.is_zero() || !account.code.is_empty()
+            })
+            .map(|(address, account)| {
+                let mut stream = rlp::RlpStream::new_list(4);
+                stream.append(&account.nonce);
+                stream.append(&account.balance);
+                stream.append(&Self::storage_root(&account.storage));
+                stream.append(&web3::types::H256::from(web3::signing::keccak256(
+                    account.code.as_slice(),
+                )));
+                (address.as_bytes(), stream.out().to_vec())
+            })
+            .map(|(key, value)| (Self::nibbles(&web3::signing::keccak256(key)), value))
+            .collect();
+
+        Self::trie_root(accounts)
+    }
+
+    ///
+    /// Computes the storage root of the specified account storage.
+    ///
+    fn storage_root(storage: &HashMap<web3::types::U256, web3::types::U256>) -> web3::types::H256 {
+        let slots = storage
+            .iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(key, value)| {
+                let mut key_bytes = [0u8; 32];
+                key.to_big_endian(&mut key_bytes);
+                (
+                    Self::nibbles(&web3::signing::keccak256(&key_bytes)),
+                    rlp::encode(value).to_vec(),
+                )
+            })
+            .collect();
+
+        Self::trie_root(slots)
+    }
+
+    ///
+    /// Computes the root of the trie with the specified nibble keys and encoded values.
+    ///
+    /// The keys are hashes, so none of them is a prefix of another.
+    ///
+    fn trie_root(mut items: Vec<(Vec<u8>, Vec<u8>)>) -> web3::types::H256 {
+        items.sort();
+        let node = if items.is_empty() {
+            rlp::NULL_RLP.to_vec()
+        } else {
+            Self::encode_node(items.as_slice(), 0)
+        };
+        web3::types::H256::from(web3::signing::keccak256(node.as_slice()))
+    }
+
+    ///
+    /// Encodes the node holding the sorted items, whose keys share the first `depth` nibbles.
+    ///
+    fn encode_node(items: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+        let mut stream = rlp::RlpStream::new();
+
+        if let [(key, value)] = items {
+            stream.begin_list(2);
+            stream.append(&Self::hex_prefix(&key[depth..], true));
+            stream.append(value);
+            return stream.out().to_vec();
+        }
+
+        let (first, _) = &items[0];
+        let (last, _) = &items[items.len() - 1];
+        let shared = first[depth..]
+            .iter()
+            .zip(last[depth..].iter())
+            .take_while(|(first, last)| first == last)
+            .count();
+        if shared > 0 {
+            stream.begin_list(2);
+            stream.append(&Self::hex_prefix(&first[depth..depth + shared], false));
+            Self::append_reference(&mut stream, Self::encode_node(items, depth + shared));
+            return stream.out().to_vec();
+        }
+
+        stream.begin_list(17);
+        for nibble in 0..16 {
+            let start = items.partition_point(|(key, _)| key[depth] < nibble);
+            let end = items.partition_point(|(key, _)| key[depth] <= nibble);
+            if start == end {
+                stream.append_empty_data();
+            } else {
+                Self::append_reference(
+                    &mut stream,
+                    Self::encode_node(&items[start..end], depth + 1),
+                );
+            }
+        }
+        stream.append_empty_data();
+        stream.out().to_vec()
+    }
+
+    ///
+    /// Appends the node as is if its encoding is shorter than a hash, and its hash otherwise.
+    ///
+    fn append_reference(stream: &mut rlp::RlpStream, node: Vec<u8>) {
+        if node.len() < 32 {
+            stream.append_raw(node.as_slice(), 1);
+        } else {
+            stream.append(&web3::types::H256::from(web3::signing::keccak256(
+                node.as_slice(),
+            )));
+        }
+    }
+
+    ///
+    /// Encodes the key nibbles of a leaf or extension node with the hex prefix.
+    ///
+    fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flag = if is_leaf { 2 } else { 0 };
+        let mut bytes = Vec::with_capacity(nibbles.len() / 2 + 1);
+        let rest = if nibbles.len() % 2 == 1 {
+            bytes.push(((flag + 1) << 4) | nibbles[0]);
+            &nibbles[1..]
+        } else {
+            bytes.push(flag << 4);
+            nibbles
+        };
+        bytes.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+        bytes
+    }
+
+    ///
+    /// Splits the bytes into nibbles, the high one first.
+    ///
+    fn nibbles(bytes: &[u8]) -> Vec<u8> {
+        bytes
+            .iter()
+            .flat_map(|byte| [byte >> 4, byte & 0x0f])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use crate::vm::evm_account::EvmAccount;
+
+    use super::StateRoot;
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            StateRoot::compute(&HashMap::new()),
+            web3::types::H256::from_str(
+                "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+            )
+            .expect("Always valid")
+        );
+    }
+
+    #[test]
+    fn single_item() {
+        let root = StateRoot::trie_root(vec![(StateRoot::nibbles(b"A"), vec![b'a'; 50])]);
+
+        assert_eq!(
+            root,
+            web3::types::H256::from_str(
+                "d23786fb4a010da3ce639d66d5e904a11dbc02746d1ce25029e53290cabf28ab"
+            )
+            .expect("Always valid")
+        );
+    }
+
+    #[test]
+    fn empty_accounts_are_removed() {
+        let state = HashMap::from([(web3::types::Address::zero(), EvmAccount::default())]);
+
+        assert_eq!(
+            StateRoot::compute(&state),
+            StateRoot::compute(&HashMap::new())
+        );
+    }
+
+    #[test]
+    fn zero_slots_are_removed() {
+        let mut account = EvmAccount {
+            nonce: web3::types::U256::one(),
+            ..EvmAccount::default()
+        };
+        let state = HashMap::from([(web3::types::Address::zero(), account.clone())]);
+        let root = StateRoot::compute(&state);
+
+        account
+            .storage
+            .insert(web3::types::U256::one(), web3::types::U256::zero());
+        let state = HashMap::from([(web3::types::Address::zero(), account)]);
+        assert_eq!(StateRoot::compute(&state), root);
+    }
+}
@@ -0,0 +1,110 @@
+//!
+//! The state test VM trait.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use era_compiler_common::EVMVersion;
+
+use crate::test::case::transaction::Transaction;
+use crate::trace::Trace;
+use crate::vm::eravm::system_context::EVMContext;
+use crate::vm::evm_account::EvmAccount;
+use crate::vm::execution_result::ExecutionResult;
+
+///
+/// The VM able to run state test cases.
+///
+/// Covers the prestate population, transaction execution and post-state queries.
+///
+pub trait StateTestVm: Clone + Send + Sync {
+    ///
+    /// Clones the shared VM instance for a single case run.
+    ///
+    fn clone_for_case(vm: Arc<Self>, _evm_version: Option<EVMVersion>) -> Self {
+        (*vm).clone()
+    }
+
+    ///
+    /// Changes the balance of the specified address.
+    ///
+    fn set_balance(&mut self, address: web3::types::Address, value: web3::types::U256);
+
+    ///
+    /// Changes the nonce of the specified address.
+    ///
+    fn set_nonce(&mut self, address: web3::types::Address, value: web3::types::U256);
+
+    ///
+    /// Sets the EVM code of the specified address.
+    ///
+    fn set_code(&mut self, address: web3::types::Address, bytecode: Vec<u8>);
+
+    ///
+    /// Adds values to storage.
+    ///
+    fn populate_storage(
+        &mut self,
+        values: HashMap<(web3::types::Address, web3::types::U256), web3::types::H256>,
+    );
+
+    ///
+    /// Executes a state test transaction, committing the resulting state.
+    ///
+    fn execute(
+        &mut self,
+        name: String,
+        transaction: &Transaction,
+        system_context: &EVMContext,
+    ) -> anyhow::Result<ExecutionResult>;
+
+    ///
+    /// Executes a state test transaction like `execute`, also returning its EIP-3155 trace.
+    ///
+    fn execute_traced(
+        &mut self,
+        _name: String,
+        _transaction: &Transaction,
+        _system_context: &EVMContext,
+    ) -> anyhow::Result<(ExecutionResult, Trace)> {
+        anyhow::bail!("The VM does not support tracing")
+    }
+
+    ///
+    /// Returns the balance of the specified address.
+    ///
+    fn get_balance(&self, address: web3::types::Address) -> web3::types::U256;
+
+    ///
+    /// Returns the nonce of the specified address.
+    ///
+    fn get_nonce(&self, address: web3::types::Address) -> web3::types::U256;
+
+    ///
+    /// Returns the EVM code of the specified address, `None` if there is no code.
+    ///
+    fn get_code(&self, address: web3::types::Address) -> Option<Vec<u8>>;
+
+    ///
+    /// Returns the storage slot value of the specified address, `None` if the slot is not set.
+    ///
+    fn get_storage_slot(
+        &self,
+        address: web3::types::Address,
+        key: web3::types::U256,
+    ) -> Option<web3::types::H256>;
+
+    ///
+    /// Returns the state of all existing accounts.
+    ///
+    fn get_state(&self) -> HashMap<web3::types::Address, EvmAccount>;
+
+    ///
+    /// Returns the system contract bytecode hashes by contract name, if the VM has any.
+    ///
+    fn system_contract_hashes(&self) -> BTreeMap<String, web3::types::H256> {
+        BTreeMap::new()
+    }
+}